@@ -0,0 +1,178 @@
+//! Derive macro for `assoc_type_params::TypeParamsT`.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use syn::{DeriveInput, Type, parse_macro_input, spanned::Spanned};
+
+/// Derives `TypeParamsT` for a marker type.
+///
+/// The associated types are specified through the `#[type_params(..)]`
+/// attribute:
+///
+/// ```rust,ignore
+/// #[derive(TypeParams)]
+/// #[type_params(error = FrameworkError, input = Stdin, output = Stdout)]
+/// struct StdioEndpoint;
+/// ```
+///
+/// `state` and `logger` may also be specified, and default to `()` and
+/// `DiscardLog`:
+///
+/// ```rust,ignore
+/// #[derive(TypeParams)]
+/// #[type_params(
+///     error = FrameworkError,
+///     input = Stdin,
+///     output = Stdout,
+///     state = Config,
+///     logger = StderrLog,
+/// )]
+/// struct ConfiguredEndpoint;
+/// ```
+///
+/// Each type is also checked against the bounds of `TypeParamsConstrained`, so
+/// a type that doesn't meet them is reported at the attribute.
+#[proc_macro_derive(TypeParams, attributes(type_params))]
+pub fn type_params_derive(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+
+    type_params_impl(derive_input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Types specified in the `#[type_params(..)]` attribute.
+#[derive(Default)]
+struct TypeParamsAttr {
+    error: Option<Type>,
+    input: Option<Type>,
+    output: Option<Type>,
+    state: Option<Type>,
+    logger: Option<Type>,
+}
+
+impl TypeParamsAttr {
+    fn parse(derive_input: &DeriveInput) -> syn::Result<Self> {
+        let mut type_params_attr = TypeParamsAttr::default();
+        let mut attr_found = false;
+
+        for attr in derive_input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("type_params"))
+        {
+            attr_found = true;
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("error") {
+                    &mut type_params_attr.error
+                } else if meta.path.is_ident("input") {
+                    &mut type_params_attr.input
+                } else if meta.path.is_ident("output") {
+                    &mut type_params_attr.output
+                } else if meta.path.is_ident("state") {
+                    &mut type_params_attr.state
+                } else if meta.path.is_ident("logger") {
+                    &mut type_params_attr.logger
+                } else {
+                    return Err(meta.error(
+                        "unknown type param, expected one of: `error`, `input`, `output`, `state`, \
+                         `logger`",
+                    ));
+                };
+
+                if slot.is_some() {
+                    return Err(meta.error("type param specified more than once"));
+                }
+                *slot = Some(meta.value()?.parse::<Type>()?);
+
+                Ok(())
+            })?;
+        }
+
+        if !attr_found {
+            return Err(syn::Error::new(
+                derive_input.ident.span(),
+                "missing `#[type_params(error = .., input = .., output = ..)]` attribute",
+            ));
+        }
+
+        Ok(type_params_attr)
+    }
+}
+
+fn type_params_impl(derive_input: DeriveInput) -> syn::Result<TokenStream2> {
+    let TypeParamsAttr {
+        error,
+        input,
+        output,
+        state,
+        logger,
+    } = TypeParamsAttr::parse(&derive_input)?;
+
+    let span = derive_input.ident.span();
+    let error = required(error, "error", span)?;
+    let input = required(input, "input", span)?;
+    let output = required(output, "output", span)?;
+    let state = state.unwrap_or_else(|| syn::parse_quote!(()));
+    let logger = logger.unwrap_or_else(|| syn::parse_quote!(::assoc_type_params::DiscardLog));
+
+    let ident = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    // Each assertion is spanned to the type in the attribute, so that the compile
+    // error points at the type that doesn't meet the bound.
+    let error_assertion = quote_spanned! {error.span()=>
+        assert_app_error::<#error>();
+    };
+    let input_assertion = quote_spanned! {input.span()=>
+        assert_input::<#input>();
+    };
+    let output_assertion = quote_spanned! {output.span()=>
+        assert_output::<#output>();
+    };
+    let state_assertion = quote_spanned! {state.span()=>
+        assert_state::<#state>();
+    };
+    let logger_assertion = quote_spanned! {logger.span()=>
+        assert_logger::<#logger>();
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::assoc_type_params::TypeParamsT for #ident #ty_generics
+        #where_clause
+        {
+            type AppError = #error;
+            type Input = #input;
+            type Output = #output;
+            type State = #state;
+            type Logger = #logger;
+        }
+
+        const _: () = {
+            fn assert_app_error<T: ::std::error::Error + 'static>() {}
+            fn assert_input<T: ::assoc_type_params::Input + 'static>() {}
+            fn assert_output<T: ::assoc_type_params::Output + 'static>() {}
+            fn assert_state<T: 'static>() {}
+            fn assert_logger<T: ::assoc_type_params::Log + 'static>() {}
+
+            #[allow(dead_code)]
+            fn assert_type_params_constrained #impl_generics () #where_clause {
+                #error_assertion
+                #input_assertion
+                #output_assertion
+                #state_assertion
+                #logger_assertion
+            }
+        };
+    })
+}
+
+fn required(ty: Option<Type>, name: &str, span: Span) -> syn::Result<Type> {
+    ty.ok_or_else(|| {
+        syn::Error::new(
+            span,
+            format!("missing `{name}` in `#[type_params(..)]` attribute"),
+        )
+    })
+}
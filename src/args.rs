@@ -0,0 +1,398 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
+
+use crate::{FrameworkError, Input, TypeParams};
+
+/// Whether an argument takes a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ArgKind {
+    /// `--name value` or `--name=value`.
+    Option,
+    /// `--name`.
+    Flag,
+}
+
+/// An argument declared in an `ArgSpec`.
+#[derive(Clone, Debug)]
+struct ArgDef {
+    /// Name of the argument, without the leading `--`.
+    name: String,
+    /// Whether the argument takes a value.
+    kind: ArgKind,
+    /// Text shown for the argument in usage.
+    help: String,
+}
+
+/// Declares the options and flags that a command accepts.
+#[derive(Clone, Debug, Default)]
+pub struct ArgSpec {
+    /// Declared arguments, in the order they were declared.
+    arg_defs: Vec<ArgDef>,
+}
+
+impl ArgSpec {
+    /// Returns a new `ArgSpec` with no arguments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares an option that takes a value, `--name value`.
+    pub fn option(mut self, name: impl Into<String>, help: impl Into<String>) -> Self {
+        self.arg_defs.push(ArgDef {
+            name: name.into(),
+            kind: ArgKind::Option,
+            help: help.into(),
+        });
+        self
+    }
+
+    /// Declares a flag that doesn't take a value, `--name`.
+    pub fn flag(mut self, name: impl Into<String>, help: impl Into<String>) -> Self {
+        self.arg_defs.push(ArgDef {
+            name: name.into(),
+            kind: ArgKind::Flag,
+            help: help.into(),
+        });
+        self
+    }
+
+    /// Parses `args` against this spec.
+    ///
+    /// Returns `FrameworkError::Usage` for undeclared arguments, and for options
+    /// without a value. An option written without `=` doesn't take a following
+    /// argument that starts with `--` as its value, e.g. `--name --loud` is an
+    /// error rather than `name = "--loud"`.
+    pub fn parse<I, S>(&self, args: I) -> Result<ArgMatches, FrameworkError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ArgMatches::parse(args, |name| {
+            self.arg_defs
+                .iter()
+                .find(|arg_def| arg_def.name == name)
+                .map(|arg_def| arg_def.kind)
+                .ok_or_else(|| {
+                    FrameworkError::Usage(format!(
+                        "unknown argument `--{name}`\n\n{}",
+                        self.usage()
+                    ))
+                })
+        })
+    }
+
+    /// Returns the usage text, listing each argument.
+    pub fn usage(&self) -> String {
+        let names = self
+            .arg_defs
+            .iter()
+            .map(|arg_def| match arg_def.kind {
+                ArgKind::Option => format!("--{} <value>", arg_def.name),
+                ArgKind::Flag => format!("--{}", arg_def.name),
+            })
+            .collect::<Vec<_>>();
+        let name_width = names.iter().map(String::len).max().unwrap_or_default();
+
+        let mut usage = String::from("Arguments:");
+        names
+            .iter()
+            .zip(self.arg_defs.iter())
+            .for_each(|(name, arg_def)| {
+                usage.push_str(&format!("\n  {name:name_width$}  {}", arg_def.help));
+            });
+
+        usage
+    }
+}
+
+/// Arguments parsed from the command line.
+#[derive(Clone, Debug, Default)]
+pub struct ArgMatches {
+    /// Values of each option, by name.
+    options: BTreeMap<String, Vec<String>>,
+    /// Flags that were passed.
+    flags: BTreeSet<String>,
+    /// Arguments that are not options or flags.
+    positionals: Vec<String>,
+    /// Option values and positional arguments, in command line order.
+    values: Vec<String>,
+}
+
+impl ArgMatches {
+    /// Parses `args` without a spec.
+    ///
+    /// `--name` is an option if it is followed by a value that doesn't start
+    /// with `--`, or written as `--name=value`, otherwise it is a flag. Use
+    /// `ArgSpec::parse` when this is ambiguous.
+    ///
+    /// Returns `FrameworkError::Usage` if the same name is passed both with and
+    /// without a value, as the arguments cannot be interpreted.
+    pub fn parse_unspecified<I, S>(args: I) -> Result<Self, FrameworkError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let args = args.into_iter().map(Into::into).collect::<Vec<String>>();
+        let option_names = args
+            .iter()
+            .zip(args.iter().skip(1).map(Some).chain([None]))
+            .filter_map(|(arg, arg_next)| {
+                let name_and_value = arg.strip_prefix("--")?;
+                match name_and_value.split_once('=') {
+                    Some((name, _value)) => Some(name.to_string()),
+                    None => arg_next
+                        .filter(|arg_next| !arg_next.starts_with("--"))
+                        .map(|_| name_and_value.to_string()),
+                }
+            })
+            .collect::<BTreeSet<_>>();
+
+        Self::parse(args, |name| {
+            if option_names.contains(name) {
+                Ok(ArgKind::Option)
+            } else {
+                Ok(ArgKind::Flag)
+            }
+        })
+    }
+
+    fn parse<I, S, F>(args: I, mut arg_kind: F) -> Result<Self, FrameworkError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+        F: FnMut(&str) -> Result<ArgKind, FrameworkError>,
+    {
+        let mut arg_matches = Self::default();
+        let mut args = args.into_iter().map(Into::into).peekable();
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                args.by_ref()
+                    .for_each(|arg| arg_matches.push_positional(arg));
+                break;
+            }
+
+            let Some(name_and_value) = arg.strip_prefix("--") else {
+                arg_matches.push_positional(arg);
+                continue;
+            };
+
+            let (name, value) = match name_and_value.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (name_and_value, None),
+            };
+            match (arg_kind(name)?, value) {
+                (ArgKind::Option, Some(value)) => arg_matches.push_option(name, value),
+                (ArgKind::Option, None) => {
+                    let value = args.next_if(|arg| !arg.starts_with("--")).ok_or_else(|| {
+                        FrameworkError::Usage(format!("`--{name}` requires a value"))
+                    })?;
+                    arg_matches.push_option(name, value);
+                }
+                (ArgKind::Flag, None) => {
+                    arg_matches.flags.insert(name.to_string());
+                }
+                (ArgKind::Flag, Some(_)) => {
+                    return Err(FrameworkError::Usage(format!(
+                        "`--{name}` does not take a value"
+                    )));
+                }
+            }
+        }
+
+        Ok(arg_matches)
+    }
+
+    fn push_option(&mut self, name: &str, value: String) {
+        self.options
+            .entry(name.to_string())
+            .or_default()
+            .push(value.clone());
+        self.values.push(value);
+    }
+
+    fn push_positional(&mut self, arg: String) {
+        self.positionals.push(arg.clone());
+        self.values.push(arg);
+    }
+
+    /// Returns the last value of the option `name` parsed as `T`, if it was
+    /// passed.
+    pub fn value<T>(&self, name: &str) -> Result<Option<T>, FrameworkError>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.options
+            .get(name)
+            .and_then(|values| values.last())
+            .map(|value| parse_value(value))
+            .transpose()
+    }
+
+    /// Returns the last value of the option `name` parsed as `T`.
+    ///
+    /// Returns `FrameworkError::Usage` if the option was not passed.
+    pub fn required<T>(&self, name: &str) -> Result<T, FrameworkError>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.value(name)?
+            .ok_or_else(|| FrameworkError::Usage(format!("`--{name}` is required")))
+    }
+
+    /// Returns every value of the option `name` parsed as `T`.
+    pub fn values_of<T>(&self, name: &str) -> Result<Vec<T>, FrameworkError>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        self.options
+            .get(name)
+            .map(|values| values.iter().map(|value| parse_value(value)).collect())
+            .unwrap_or_else(|| Ok(Vec::new()))
+    }
+
+    /// Returns whether the flag `name` was passed.
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    /// Returns the arguments that are not options or flags.
+    pub fn positionals(&self) -> &[String] {
+        &self.positionals
+    }
+
+    /// Returns option values and positional arguments, in command line order.
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+}
+
+/// Type that is filled in from command line arguments.
+pub trait FromArgs: Sized {
+    /// Returns the options and flags that this type is filled in from.
+    fn arg_spec() -> ArgSpec;
+
+    /// Fills in this type from parsed arguments.
+    fn from_arg_matches(arg_matches: &ArgMatches) -> Result<Self, FrameworkError>;
+
+    /// Parses `args` against `arg_spec()`, and fills in this type.
+    fn from_args<I, S>(args: I) -> Result<Self, FrameworkError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let arg_matches = Self::arg_spec().parse(args)?;
+        Self::from_arg_matches(&arg_matches)
+    }
+}
+
+/// `Input` that reads command line argument values in order.
+///
+/// Each option value and positional argument is read as a line, so the same
+/// logic can run non-interactively, e.g. `app --value foo` reads `foo`.
+#[derive(Clone, Debug)]
+pub struct ArgsInput {
+    /// Parsed arguments.
+    arg_matches: ArgMatches,
+    /// Index of the next value to read.
+    next: usize,
+}
+
+impl ArgsInput {
+    /// Returns a new `ArgsInput` for `args`, which should not include the
+    /// program name.
+    ///
+    /// Returns `FrameworkError::Usage` if the arguments cannot be interpreted,
+    /// see `ArgMatches::parse_unspecified`.
+    pub fn new<I, S>(args: I) -> Result<Self, FrameworkError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        ArgMatches::parse_unspecified(args).map(Self::from)
+    }
+
+    /// Returns a new `ArgsInput` for `args` parsed against `arg_spec`.
+    pub fn with_spec<I, S>(arg_spec: &ArgSpec, args: I) -> Result<Self, FrameworkError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        arg_spec.parse(args).map(Self::from)
+    }
+
+    /// Returns a new `ArgsInput` for the process's arguments.
+    ///
+    /// Returns `FrameworkError::Usage` if an argument is not valid UTF-8.
+    pub fn from_env() -> Result<Self, FrameworkError> {
+        let args = std::env::args_os()
+            .skip(1)
+            .map(|arg| {
+                arg.into_string().map_err(|arg| {
+                    FrameworkError::Usage(format!(
+                        "argument `{}` is not valid UTF-8",
+                        arg.to_string_lossy()
+                    ))
+                })
+            })
+            .collect::<Result<Vec<String>, FrameworkError>>()?;
+
+        Self::new(args)
+    }
+
+    /// Returns the parsed arguments.
+    pub fn arg_matches(&self) -> &ArgMatches {
+        &self.arg_matches
+    }
+
+    /// Fills in `T` from the parsed arguments.
+    pub fn parse<T>(&self) -> Result<T, FrameworkError>
+    where
+        T: FromArgs,
+    {
+        T::from_arg_matches(&self.arg_matches)
+    }
+}
+
+impl From<ArgMatches> for ArgsInput {
+    fn from(arg_matches: ArgMatches) -> Self {
+        Self {
+            arg_matches,
+            next: 0,
+        }
+    }
+}
+
+impl Input for ArgsInput {
+    fn read(&mut self) -> Result<String, FrameworkError> {
+        let value = self
+            .arg_matches
+            .values
+            .get(self.next)
+            .ok_or(FrameworkError::EndOfInput)?;
+        self.next += 1;
+
+        Ok(format!("{value}\n"))
+    }
+}
+
+/// Type params marker for reading command line arguments, and writing to
+/// stdout.
+#[derive(TypeParams)]
+#[type_params(error = FrameworkError, input = ArgsInput, output = std::io::Stdout)]
+pub struct ArgsEndpoint;
+
+fn parse_value<T>(value: &str) -> Result<T, FrameworkError>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    value.parse::<T>().map_err(|error| FrameworkError::Parse {
+        input: value.to_string(),
+        error: Box::new(error),
+    })
+}
@@ -0,0 +1,303 @@
+use std::{
+    future::Future,
+    io::{Stdin, Stdout},
+};
+
+use crate::{
+    CmdCtx, CmdCtxBuilder, FrameworkError, Input, Level, Log, Logic, Output, TypeParams,
+    TypeParamsT,
+};
+
+// === Traits for pluggable async types === //
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as the `AsyncInput` type",
+    label = "this type does not implement `AsyncInput`",
+    note = "a blocking `Input` can be used through `AsyncAdapter`"
+)]
+pub trait AsyncInput {
+    /// Reads the next line, including its trailing newline if there is one.
+    ///
+    /// Resolves to `FrameworkError::EndOfInput` when there is no more input.
+    fn read(&mut self) -> impl Future<Output = Result<String, FrameworkError>>;
+}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as the `AsyncOutput` type",
+    label = "this type does not implement `AsyncOutput`",
+    note = "a blocking `Output` can be used through `AsyncAdapter`"
+)]
+pub trait AsyncOutput {
+    fn write(&mut self, s: &str) -> impl Future<Output = Result<(), FrameworkError>>;
+
+    /// Flushes buffered writes.
+    fn flush(&mut self) -> impl Future<Output = Result<(), FrameworkError>> {
+        async { Ok(()) }
+    }
+
+    /// Called when the context finishes, to flush or commit what was written.
+    fn finish(&mut self) -> impl Future<Output = Result<(), FrameworkError>> {
+        async { Ok(()) }
+    }
+}
+
+pub trait AsyncLogic {
+    type ReturnType;
+    type Error: std::error::Error;
+
+    fn do_work(&mut self) -> impl Future<Output = Result<Self::ReturnType, Self::Error>>;
+}
+
+/// Similar to `TypeParamsConstrained`, with async bounds on the input and
+/// output types.
+///
+/// This lets a `CmdCtx<Types>` carry async endpoints, while still being tracked
+/// by a single `TypeParamsT` marker.
+pub trait AsyncTypeParamsConstrained:
+    TypeParamsT<
+        AppError = <Self as AsyncTypeParamsConstrained>::AppError,
+        Input = <Self as AsyncTypeParamsConstrained>::Input,
+        Output = <Self as AsyncTypeParamsConstrained>::Output,
+        State = <Self as AsyncTypeParamsConstrained>::State,
+        Logger = <Self as AsyncTypeParamsConstrained>::Logger,
+    >
+{
+    type AppError: std::error::Error + 'static;
+    type Input: AsyncInput + 'static;
+    type Output: AsyncOutput + 'static;
+    type State: 'static;
+    type Logger: Log + 'static;
+}
+
+impl<T> AsyncTypeParamsConstrained for T
+where
+    T: TypeParamsT,
+    T::AppError: std::error::Error + 'static,
+    T::Input: AsyncInput + 'static,
+    T::Output: AsyncOutput + 'static,
+    T::State: 'static,
+    T::Logger: Log + 'static,
+{
+    type AppError = T::AppError;
+    type Input = T::Input;
+    type Output = T::Output;
+    type State = T::State;
+    type Logger = T::Logger;
+}
+
+/// Type params marker for stdin and stdout, used from the async path.
+pub type AsyncStdioEndpoint = TypeParams<FrameworkError, AsyncAdapter<Stdin>, AsyncAdapter<Stdout>>;
+
+// === Builder === //
+
+impl<Types> CmdCtxBuilder<Types>
+where
+    Types: AsyncTypeParamsConstrained,
+{
+    /// Returns the `CmdCtx` with all of its async types set.
+    pub fn build_async(self) -> CmdCtx<Types> {
+        let CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        } = self;
+
+        CmdCtx {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        }
+    }
+}
+
+// === Adapters === //
+
+/// Adapts a blocking `Input`, `Output`, or `Logic` to be used from the async
+/// path.
+///
+/// The blocking call is made within the future, so this will block the
+/// executor thread while it runs.
+#[derive(Debug)]
+pub struct AsyncAdapter<T>(pub T);
+
+impl<T> AsyncInput for AsyncAdapter<T>
+where
+    T: Input,
+{
+    async fn read(&mut self) -> Result<String, FrameworkError> {
+        self.0.read()
+    }
+}
+
+impl<T> AsyncOutput for AsyncAdapter<T>
+where
+    T: Output,
+{
+    async fn write(&mut self, s: &str) -> Result<(), FrameworkError> {
+        self.0.write(s)
+    }
+
+    async fn flush(&mut self) -> Result<(), FrameworkError> {
+        self.0.flush()
+    }
+
+    async fn finish(&mut self) -> Result<(), FrameworkError> {
+        self.0.finish()
+    }
+}
+
+impl<T> AsyncLogic for AsyncAdapter<T>
+where
+    T: Logic,
+{
+    type Error = T::Error;
+    type ReturnType = T::ReturnType;
+
+    async fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        self.0.do_work()
+    }
+}
+
+/// Adapts an `AsyncInput`, `AsyncOutput`, or `AsyncLogic` to be used from the
+/// blocking path.
+///
+/// Each call blocks the current thread until the future completes, so this
+/// must not be used from within an async executor.
+#[derive(Debug)]
+pub struct BlockingAdapter<T>(pub T);
+
+impl<T> Input for BlockingAdapter<T>
+where
+    T: AsyncInput,
+{
+    fn read(&mut self) -> Result<String, FrameworkError> {
+        futures_executor::block_on(self.0.read())
+    }
+}
+
+impl<T> Output for BlockingAdapter<T>
+where
+    T: AsyncOutput,
+{
+    fn write(&mut self, s: &str) -> Result<(), FrameworkError> {
+        futures_executor::block_on(self.0.write(s))
+    }
+
+    fn flush(&mut self) -> Result<(), FrameworkError> {
+        futures_executor::block_on(self.0.flush())
+    }
+
+    fn finish(&mut self) -> Result<(), FrameworkError> {
+        futures_executor::block_on(self.0.finish())
+    }
+}
+
+impl<T> Logic for BlockingAdapter<T>
+where
+    T: AsyncLogic,
+{
+    type Error = T::Error;
+    type ReturnType = T::ReturnType;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        futures_executor::block_on(self.0.do_work())
+    }
+}
+
+// === User level logic === //
+
+/// Async version of `run`.
+///
+/// Like `run`, the cancel token is checked between steps, the output is flushed
+/// before returning, and each step is recorded in a logger span.
+///
+/// The read and work timeouts are not applied, as each step is a future that
+/// runs until it completes. Use the executor's timers to bound them instead.
+pub async fn run_async<Types, L>(
+    cmd_ctx: &mut CmdCtx<Types>,
+    logic: &mut L,
+) -> Result<L::ReturnType, <Types as AsyncTypeParamsConstrained>::AppError>
+where
+    Types: AsyncTypeParamsConstrained,
+    L: AsyncLogic,
+    <Types as AsyncTypeParamsConstrained>::AppError: From<L::Error> + From<FrameworkError>,
+{
+    cmd_ctx.logger.span_enter("run");
+    let result = run_async_steps(cmd_ctx, logic).await;
+    cmd_ctx.logger.span_exit("run");
+
+    // Like `flush_then`, the error that stopped the command is returned even if
+    // flushing fails.
+    match result {
+        Ok(t) => {
+            cmd_ctx.output.flush().await?;
+            Ok(t)
+        }
+        Err(error) => {
+            let _ = cmd_ctx.output.flush().await;
+            Err(error)
+        }
+    }
+}
+
+async fn run_async_steps<Types, L>(
+    cmd_ctx: &mut CmdCtx<Types>,
+    logic: &mut L,
+) -> Result<L::ReturnType, <Types as AsyncTypeParamsConstrained>::AppError>
+where
+    Types: AsyncTypeParamsConstrained,
+    L: AsyncLogic,
+    <Types as AsyncTypeParamsConstrained>::AppError: From<L::Error> + From<FrameworkError>,
+{
+    let CmdCtx {
+        input,
+        output,
+        logger,
+        cancel_token,
+        ..
+    } = cmd_ctx;
+
+    // Spans are entered and exited around each `await`, as `Log::in_span` takes
+    // a blocking closure.
+    logger.span_enter("write");
+    let written = output.write("Enter some input:\n").await;
+    logger.span_exit("write");
+    written?;
+
+    cancel_token.check()?;
+    logger.span_enter("read");
+    let line = input.read().await;
+    logger.span_exit("read");
+    let line = line?;
+    logger.event(Level::Debug, &format!("read {} bytes", line.len()));
+
+    cancel_token.check()?;
+    logger.span_enter("work");
+    let t = logic.do_work().await;
+    if let Err(error) = &t {
+        logger.event(Level::Error, &format!("logic failed: {error}"));
+    }
+    logger.span_exit("work");
+    let t = t?;
+
+    cancel_token.check()?;
+    logger.span_enter("write");
+    let written = match output.write("You entered: ").await {
+        Ok(()) => output.write(&line).await,
+        Err(error) => Err(error),
+    };
+    logger.span_exit("write");
+    written?;
+
+    Ok(t)
+}
@@ -0,0 +1,141 @@
+use std::{
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    CmdCtx, CmdCtxBuilder, FrameworkError, TimeoutPhase, TypeParamsT, exit_code::EXIT_INTERRUPTED,
+};
+
+/// Token that logic polls to stop cooperatively when the command is
+/// cancelled, e.g. by Ctrl-C, or when the current phase's deadline passes.
+///
+/// Clones share the same state, so a clone can be moved into logic or another
+/// thread, and cancelled from anywhere.
+#[derive(Clone, Debug, Default)]
+pub struct CancelToken {
+    /// Whether the command is cancelled.
+    cancelled: Arc<AtomicBool>,
+    /// Deadline of the current phase, if it has a timeout.
+    deadline: Arc<Mutex<Option<(TimeoutPhase, Instant)>>>,
+}
+
+impl CancelToken {
+    /// Returns a new `CancelToken` that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the command.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns whether the command is cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns the current phase and its deadline, if it has a timeout.
+    pub fn deadline(&self) -> Option<(TimeoutPhase, Instant)> {
+        *self.deadline.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns `FrameworkError::Interrupted` if the command is cancelled, or
+    /// `FrameworkError::Timeout` if the current phase's deadline has passed.
+    ///
+    /// Logic calls this between units of work, and returns the error to stop.
+    pub fn check(&self) -> Result<(), FrameworkError> {
+        if self.is_cancelled() {
+            return Err(FrameworkError::Interrupted);
+        }
+
+        match self.deadline() {
+            Some((phase, deadline)) if Instant::now() >= deadline => {
+                Err(FrameworkError::Timeout(phase))
+            }
+            Some(_) | None => Ok(()),
+        }
+    }
+
+    /// Runs `f` with a deadline of `timeout` from now for `phase`.
+    ///
+    /// The deadline is cooperative: `f` only stops at it if `f` checks the
+    /// token, and whatever `f` returns is returned, even if the deadline has
+    /// passed.
+    ///
+    /// If a deadline is already set and is earlier, it is kept, so a read
+    /// within logic still stops at the logic's deadline. The previous deadline
+    /// is restored afterwards.
+    pub fn with_timeout<T, F>(&self, phase: TimeoutPhase, timeout: Option<Duration>, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        let Some(timeout) = timeout else {
+            return f();
+        };
+
+        let deadline_previous = self.deadline();
+        let deadline = Instant::now() + timeout;
+        let deadline_next = match deadline_previous {
+            Some((phase_previous, deadline_previous)) if deadline_previous <= deadline => {
+                (phase_previous, deadline_previous)
+            }
+            Some(_) | None => (phase, deadline),
+        };
+        self.set_deadline(Some(deadline_next));
+
+        let t = f();
+        self.set_deadline(deadline_previous);
+
+        t
+    }
+
+    fn set_deadline(&self, deadline: Option<(TimeoutPhase, Instant)>) {
+        *self.deadline.lock().unwrap_or_else(PoisonError::into_inner) = deadline;
+    }
+
+    /// Cancels this token when the process receives `SIGINT` or `SIGTERM`.
+    ///
+    /// If the process receives either signal again after the token is
+    /// cancelled, it restores terminal echo if a secret is being read, and
+    /// exits straight away with status `130`.
+    ///
+    /// Signal handlers can only be set once per process, so this returns an
+    /// error if a handler is already set.
+    pub fn handle_signals(&self) -> std::io::Result<()> {
+        let cancel_token = self.clone();
+        ctrlc::set_handler(move || {
+            if cancel_token.is_cancelled() {
+                crate::secret::restore_terminal();
+                std::process::exit(i32::from(EXIT_INTERRUPTED));
+            }
+            cancel_token.cancel();
+        })
+        .map_err(std::io::Error::other)
+    }
+}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsT,
+{
+    /// Returns the token that is cancelled when the command should stop.
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel_token
+    }
+}
+
+impl<Types> CmdCtxBuilder<Types>
+where
+    Types: TypeParamsT,
+{
+    /// Sets the token that is cancelled when the command should stop.
+    pub fn with_cancel_token(mut self, cancel_token: CancelToken) -> Self {
+        self.cancel_token = cancel_token;
+        self
+    }
+}
@@ -0,0 +1,267 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::{CmdCtx, CtxLogic, FrameworkError, Logic, Presentable, TypeParamsConstrained};
+
+/// Function that runs a registered command with its arguments.
+type CommandFn<Types> = Box<
+    dyn FnMut(
+        &mut CmdCtx<Types>,
+        &str,
+    ) -> Result<Box<dyn Presentable>, <Types as TypeParamsConstrained>::AppError>,
+>;
+
+/// A registered command.
+struct Command<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Text shown for the command in help.
+    help: String,
+    /// Runs the command.
+    run: CommandFn<Types>,
+}
+
+/// Registry of commands, each of which runs a `Logic` against a shared
+/// `CmdCtx<Types>`.
+///
+/// Each command's return value is boxed as a `Presentable`, so commands with
+/// different return types can be run through the same registry.
+pub struct Commands<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Commands by name.
+    commands: BTreeMap<String, Command<Types>>,
+}
+
+impl<Types> Commands<Types>
+where
+    Types: TypeParamsConstrained,
+    <Types as TypeParamsConstrained>::AppError: From<FrameworkError>,
+{
+    /// Returns a new `Commands` with no commands.
+    pub fn new() -> Self {
+        Self {
+            commands: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `logic` to run for the command `name`.
+    ///
+    /// Any arguments after the command name are ignored.
+    pub fn with_command<L>(
+        mut self,
+        name: impl Into<String>,
+        help: impl Into<String>,
+        mut logic: L,
+    ) -> Self
+    where
+        L: Logic + 'static,
+        L::ReturnType: Presentable + 'static,
+        <Types as TypeParamsConstrained>::AppError: From<L::Error>,
+    {
+        let run: CommandFn<Types> = Box::new(move |_cmd_ctx, _args| {
+            let t = logic.do_work()?;
+
+            Ok(Box::new(t))
+        });
+        self.commands.insert(
+            name.into(),
+            Command {
+                help: help.into(),
+                run,
+            },
+        );
+        self
+    }
+
+    /// Registers `logic` to run for the command `name`, with the arguments
+    /// after the command name parsed as its input.
+    pub fn with_ctx_command<L>(
+        mut self,
+        name: impl Into<String>,
+        help: impl Into<String>,
+        mut logic: L,
+    ) -> Self
+    where
+        L: CtxLogic<Types> + 'static,
+        L::Input: FromStr,
+        <L::Input as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+        L::ReturnType: Presentable + 'static,
+        <Types as TypeParamsConstrained>::AppError: From<L::Error>,
+    {
+        let run: CommandFn<Types> = Box::new(move |cmd_ctx, args| {
+            let input = args
+                .parse::<L::Input>()
+                .map_err(|error| FrameworkError::Parse {
+                    input: args.to_string(),
+                    error: Box::new(error),
+                })?;
+            let t = logic.do_work(cmd_ctx, input)?;
+
+            Ok(Box::new(t))
+        });
+        self.commands.insert(
+            name.into(),
+            Command {
+                help: help.into(),
+                run,
+            },
+        );
+        self
+    }
+
+    /// Returns whether a command is registered with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.commands.contains_key(name)
+    }
+
+    /// Returns the help text for the command `name`.
+    pub fn help(&self, name: &str) -> Option<&str> {
+        self.commands.get(name).map(|command| command.help.as_str())
+    }
+
+    /// Returns each command name and its help text, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.commands
+            .iter()
+            .map(|(name, command)| (name.as_str(), command.help.as_str()))
+    }
+
+    /// Runs the command `name` with the given arguments.
+    ///
+    /// Returns `None` if there is no command with that name.
+    pub fn run(
+        &mut self,
+        cmd_ctx: &mut CmdCtx<Types>,
+        name: &str,
+        args: &str,
+    ) -> Option<Result<Box<dyn Presentable>, <Types as TypeParamsConstrained>::AppError>> {
+        self.commands
+            .get_mut(name)
+            .map(|command| (command.run)(cmd_ctx, args))
+    }
+
+    /// Returns the command names and their help text as an aligned list.
+    pub(crate) fn help_list<'f>(
+        &'f self,
+        extra: impl IntoIterator<Item = (&'f str, &'f str)>,
+    ) -> String {
+        let entries = self.iter().chain(extra).collect::<Vec<_>>();
+        let name_width = entries
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or_default();
+
+        entries
+            .into_iter()
+            .map(|(name, help)| format!("  {name:name_width$}  {help}\n"))
+            .collect()
+    }
+}
+
+impl<Types> Default for Commands<Types>
+where
+    Types: TypeParamsConstrained,
+    <Types as TypeParamsConstrained>::AppError: From<FrameworkError>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Runs the command selected by command line arguments.
+///
+/// The first argument is the command name, and the remaining arguments are
+/// joined with spaces and passed to the command. `help` lists the commands, and
+/// `help <command>` shows the help text for a command.
+pub struct Dispatcher<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Name of the program, shown in usage.
+    program: String,
+    /// Commands that can be dispatched to.
+    commands: Commands<Types>,
+}
+
+impl<Types> Dispatcher<Types>
+where
+    Types: TypeParamsConstrained,
+    <Types as TypeParamsConstrained>::AppError: From<FrameworkError>,
+{
+    /// Command that shows help text.
+    pub const HELP: &'static str = "help";
+
+    /// Returns a new `Dispatcher` for the given commands.
+    pub fn new(program: impl Into<String>, commands: Commands<Types>) -> Self {
+        Self {
+            program: program.into(),
+            commands,
+        }
+    }
+
+    /// Runs the command selected by `args`, which should not include the
+    /// program name.
+    ///
+    /// Returns `FrameworkError::Usage` if no command, or an unknown command is
+    /// given.
+    pub fn dispatch<I, S>(
+        &mut self,
+        cmd_ctx: &mut CmdCtx<Types>,
+        args: I,
+    ) -> Result<Box<dyn Presentable>, <Types as TypeParamsConstrained>::AppError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut args = args.into_iter();
+        let Some(name) = args.next() else {
+            return Err(FrameworkError::Usage(self.usage()).into());
+        };
+        let name = name.as_ref();
+        let args = args
+            .map(|arg| arg.as_ref().to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if name == Self::HELP {
+            return Ok(Box::new(self.help(&args)?));
+        }
+
+        self.commands.run(cmd_ctx, name, &args).unwrap_or_else(|| {
+            Err(
+                FrameworkError::Usage(format!("unknown command `{name}`\n\n{}", self.usage()))
+                    .into(),
+            )
+        })
+    }
+
+    /// Returns the usage text, listing each command.
+    pub fn usage(&self) -> String {
+        let command_list = self
+            .commands
+            .help_list([(Self::HELP, "Lists commands, or shows help for a command.")]);
+
+        format!(
+            "Usage: {} <command> [args]\n\nCommands:\n{}",
+            self.program,
+            command_list.trim_end()
+        )
+    }
+
+    /// Returns the help text for `name`, or the usage text if `name` is empty.
+    fn help(&self, name: &str) -> Result<String, FrameworkError> {
+        if name.is_empty() {
+            return Ok(self.usage());
+        }
+
+        self.commands
+            .help(name)
+            .map(|help| format!("{name}: {help}"))
+            .ok_or_else(|| {
+                FrameworkError::Usage(format!("unknown command `{name}`\n\n{}", self.usage()))
+            })
+    }
+}
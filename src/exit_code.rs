@@ -0,0 +1,104 @@
+use crate::{
+    CmdCtx, Diagnostic, FrameworkError, LogicError, Presentable, TypeParamsConstrained,
+    sysexits::{EX_DATAERR, EX_IOERR, EX_NOINPUT, EX_SOFTWARE, EX_TEMPFAIL, EX_USAGE},
+};
+
+/// Exit codes from `sysexits.h`.
+pub mod sysexits {
+    /// Successful termination.
+    pub const EX_OK: u8 = 0;
+    /// Command was used incorrectly, e.g. with the wrong number of arguments.
+    pub const EX_USAGE: u8 = 64;
+    /// Input data was incorrect in some way.
+    pub const EX_DATAERR: u8 = 65;
+    /// An input file did not exist or was not readable.
+    pub const EX_NOINPUT: u8 = 66;
+    /// An internal software error has been detected.
+    pub const EX_SOFTWARE: u8 = 70;
+    /// An error occurred while doing I/O on some file.
+    pub const EX_IOERR: u8 = 74;
+    /// Temporary failure, the command may succeed if it is retried.
+    pub const EX_TEMPFAIL: u8 = 75;
+}
+
+/// Exit status when the command is interrupted, `128 + SIGINT` as reported by
+/// shells.
+pub(crate) const EXIT_INTERRUPTED: u8 = 130;
+
+/// Maps an error to the process exit status.
+///
+/// Implement this for the `AppError` to use it with `run_main`; the default
+/// implementation returns `1`.
+pub trait ToExitCode: std::error::Error {
+    /// Returns the process exit status for this error.
+    fn exit_code(&self) -> u8 {
+        1
+    }
+}
+
+impl ToExitCode for LogicError {
+    fn exit_code(&self) -> u8 {
+        EX_SOFTWARE
+    }
+}
+
+impl ToExitCode for FrameworkError {
+    fn exit_code(&self) -> u8 {
+        match self {
+            FrameworkError::Logic(_) => EX_SOFTWARE,
+            FrameworkError::Input(_) => EX_NOINPUT,
+            FrameworkError::Output(_) => EX_IOERR,
+            FrameworkError::EndOfInput => EX_NOINPUT,
+            FrameworkError::Usage(_) => EX_USAGE,
+            FrameworkError::Present(_) => EX_SOFTWARE,
+            FrameworkError::Parse { input: _, error: _ } => EX_DATAERR,
+            FrameworkError::Resource(_) => EX_SOFTWARE,
+            FrameworkError::Interrupted => EXIT_INTERRUPTED,
+            FrameworkError::Timeout(_) => EX_TEMPFAIL,
+            FrameworkError::Retry {
+                history: _,
+                error: _,
+            } => EX_TEMPFAIL,
+        }
+    }
+}
+
+/// Runs `f`, presents its return value or error, and returns the process exit
+/// status.
+///
+/// The context is finished if the command succeeds, and aborted if it fails, so
+/// output that is committed on finish, such as an atomic `FileOutput`, is only
+/// committed for successful commands. The error is written with
+/// `Output::write_error`, so it is still seen when the output is discarded.
+///
+/// This is intended to be returned from `main`:
+///
+/// ```rust,ignore
+/// fn main() -> std::process::ExitCode {
+///     let mut cmd_ctx = CmdCtxBuilder::new()
+///         // ..
+///         .build();
+///
+///     run_main(&mut cmd_ctx, |cmd_ctx| run(cmd_ctx, &mut WorkLogic))
+/// }
+/// ```
+pub fn run_main<Types, F, T>(cmd_ctx: &mut CmdCtx<Types>, f: F) -> std::process::ExitCode
+where
+    Types: TypeParamsConstrained,
+    <Types as TypeParamsConstrained>::AppError: Diagnostic + ToExitCode + From<FrameworkError>,
+    F: FnOnce(&mut CmdCtx<Types>) -> Result<T, <Types as TypeParamsConstrained>::AppError>,
+    T: Presentable,
+{
+    let result = f(cmd_ctx)
+        .and_then(|t| Ok(cmd_ctx.present(&t)?))
+        .and_then(|()| Ok(cmd_ctx.finish()?));
+    match result {
+        Ok(()) => std::process::ExitCode::SUCCESS,
+        Err(error) => {
+            // If the error cannot be written, there is nowhere else to report it.
+            let _ = cmd_ctx.present_error(&error);
+            let _ = cmd_ctx.abort();
+            std::process::ExitCode::from(error.exit_code())
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{FrameworkError, Input, Output, TypeParams};
+
+/// `Input` that reads lines from a file.
+#[derive(Debug)]
+pub struct FileInput {
+    /// Path to the file.
+    path: PathBuf,
+    /// Buffered reader over the file.
+    reader: BufReader<File>,
+}
+
+impl FileInput {
+    /// Opens the file at `path` for reading.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, FrameworkError> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path).map_err(FrameworkError::Input)?;
+        let reader = BufReader::new(file);
+
+        Ok(Self { path, reader })
+    }
+
+    /// Returns the path to the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Input for FileInput {
+    fn read(&mut self) -> Result<String, FrameworkError> {
+        let mut buffer = String::with_capacity(256);
+        let n = self
+            .reader
+            .read_line(&mut buffer)
+            .map_err(FrameworkError::Input)?;
+
+        if n == 0 {
+            Err(FrameworkError::EndOfInput)
+        } else {
+            Ok(buffer)
+        }
+    }
+}
+
+/// How a `FileOutput` opens its file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileOutputMode {
+    /// Creates a new file, failing if the file already exists.
+    Create,
+    /// Appends to the file, creating it if it doesn't exist.
+    Append,
+    /// Truncates the file, creating it if it doesn't exist.
+    Truncate,
+}
+
+/// `Output` that writes to a file.
+///
+/// When atomic, writes go to a temporary file next to the destination, which is
+/// renamed over the destination in `finish`. If the `FileOutput` is aborted or
+/// dropped without finishing, the temporary file is removed and the destination
+/// is left untouched.
+///
+/// Error reports are written to stderr, so that they are neither mixed into the
+/// file nor discarded with it.
+pub struct FileOutput {
+    /// Path to the destination file.
+    path: PathBuf,
+    /// Path to the temporary file, if writes are atomic.
+    path_tmp: Option<PathBuf>,
+    /// How the file is opened, which for atomic writes also decides how the
+    /// destination is replaced.
+    mode: FileOutputMode,
+    /// Buffered writer over the file that is written to.
+    writer: BufWriter<File>,
+    /// Whether `finish` has completed.
+    finished: bool,
+    /// Where error reports are written.
+    error_writer: Box<dyn Write>,
+}
+
+impl fmt::Debug for FileOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileOutput")
+            .field("path", &self.path)
+            .field("path_tmp", &self.path_tmp)
+            .field("mode", &self.mode)
+            .field("writer", &self.writer)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FileOutput {
+    /// Opens the file at `path` for writing.
+    pub fn open(path: impl AsRef<Path>, mode: FileOutputMode) -> Result<Self, FrameworkError> {
+        let path = path.as_ref().to_path_buf();
+        let file = open_options(mode)
+            .open(&path)
+            .map_err(FrameworkError::Output)?;
+
+        Ok(Self {
+            path,
+            path_tmp: None,
+            mode,
+            writer: BufWriter::new(file),
+            finished: false,
+            error_writer: Box::new(std::io::stderr()),
+        })
+    }
+
+    /// Opens a temporary file for writing, which replaces the file at `path`
+    /// when the output is finished.
+    pub fn open_atomic(
+        path: impl AsRef<Path>,
+        mode: FileOutputMode,
+    ) -> Result<Self, FrameworkError> {
+        let path = path.as_ref().to_path_buf();
+        let path_tmp = tmp_path(&path);
+
+        match mode {
+            FileOutputMode::Create if path.exists() => {
+                return Err(FrameworkError::Output(std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    format!("`{}` already exists", path.display()),
+                )));
+            }
+            FileOutputMode::Append if path.exists() => {
+                fs::copy(&path, &path_tmp).map_err(FrameworkError::Output)?;
+            }
+            FileOutputMode::Create | FileOutputMode::Append | FileOutputMode::Truncate => {}
+        }
+
+        let file = open_options(match mode {
+            FileOutputMode::Append => FileOutputMode::Append,
+            FileOutputMode::Create | FileOutputMode::Truncate => FileOutputMode::Truncate,
+        })
+        .open(&path_tmp)
+        .map_err(FrameworkError::Output)?;
+
+        Ok(Self {
+            path,
+            path_tmp: Some(path_tmp),
+            mode,
+            writer: BufWriter::new(file),
+            finished: false,
+            error_writer: Box::new(std::io::stderr()),
+        })
+    }
+
+    /// Sets where error reports are written, instead of stderr.
+    pub fn with_error_writer(mut self, error_writer: impl Write + 'static) -> Self {
+        self.error_writer = Box::new(error_writer);
+        self
+    }
+
+    /// Returns the path to the destination file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Output for FileOutput {
+    fn write(&mut self, s: &str) -> Result<(), FrameworkError> {
+        self.writer
+            .write_all(s.as_bytes())
+            .map_err(FrameworkError::Output)
+    }
+
+    /// Writes the error report to stderr, or the writer set with
+    /// `with_error_writer`.
+    fn write_error(&mut self, s: &str) -> Result<(), FrameworkError> {
+        self.error_writer
+            .write_all(s.as_bytes())
+            .and_then(|()| self.error_writer.flush())
+            .map_err(FrameworkError::Output)
+    }
+
+    fn flush(&mut self) -> Result<(), FrameworkError> {
+        self.writer.flush().map_err(FrameworkError::Output)
+    }
+
+    /// Flushes the file, and renames the temporary file over the destination if
+    /// the output is atomic.
+    ///
+    /// In `FileOutputMode::Create`, the destination is only created if it still
+    /// doesn't exist, even if it was created after the output was opened.
+    fn finish(&mut self) -> Result<(), FrameworkError> {
+        if self.finished {
+            return Ok(());
+        }
+
+        self.writer.flush().map_err(FrameworkError::Output)?;
+        if let Some(path_tmp) = self.path_tmp.as_ref() {
+            self.writer
+                .get_ref()
+                .sync_all()
+                .map_err(FrameworkError::Output)?;
+            match self.mode {
+                FileOutputMode::Create => {
+                    // `hard_link` fails if the destination exists, unlike
+                    // `rename` which replaces it.
+                    fs::hard_link(path_tmp, &self.path).map_err(FrameworkError::Output)?;
+                    self.finished = true;
+                    fs::remove_file(path_tmp).map_err(FrameworkError::Output)?;
+                }
+                FileOutputMode::Append | FileOutputMode::Truncate => {
+                    fs::rename(path_tmp, &self.path).map_err(FrameworkError::Output)?;
+                }
+            }
+        }
+        self.finished = true;
+
+        Ok(())
+    }
+
+    /// Flushes the file, leaving the destination untouched if the output is
+    /// atomic.
+    ///
+    /// The temporary file is removed when the output is dropped.
+    fn abort(&mut self) -> Result<(), FrameworkError> {
+        self.writer.flush().map_err(FrameworkError::Output)
+    }
+}
+
+impl Drop for FileOutput {
+    fn drop(&mut self) {
+        if let (Some(path_tmp), false) = (self.path_tmp.as_ref(), self.finished) {
+            // Nothing can be done if the temporary file cannot be removed.
+            let _ = fs::remove_file(path_tmp);
+        }
+    }
+}
+
+fn open_options(mode: FileOutputMode) -> OpenOptions {
+    let mut open_options = OpenOptions::new();
+    match mode {
+        FileOutputMode::Create => open_options.write(true).create_new(true),
+        FileOutputMode::Append => open_options.append(true).create(true),
+        FileOutputMode::Truncate => open_options.write(true).create(true).truncate(true),
+    };
+
+    open_options
+}
+
+/// Returns the path of the temporary file for an atomic write to `path`.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut file_name = std::ffi::OsString::from(".");
+    file_name.push(path.file_name().unwrap_or_default());
+    file_name.push(".tmp");
+
+    path.with_file_name(file_name)
+}
+
+/// Type params marker for reading from and writing to files, e.g. for batch
+/// jobs.
+#[derive(TypeParams)]
+#[type_params(error = FrameworkError, input = FileInput, output = FileOutput)]
+pub struct FileEndpoint;
@@ -0,0 +1,110 @@
+use std::str::FromStr;
+
+use crate::{CmdCtx, FrameworkError, Input, Log, Output, TypeParamsConstrained, timeout};
+
+/// Typed parsing on top of `Input`.
+pub trait InputExt: Input {
+    /// Reads a line, trims the trailing newline, and parses it as `T`.
+    ///
+    /// If parsing fails, the returned `FrameworkError::Parse` holds the text
+    /// that was entered.
+    fn read_parsed<T>(&mut self) -> Result<T, FrameworkError>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        let line = self.read()?;
+        parse_line(&line)
+    }
+
+    /// Reads and parses a line as `T`, re-prompting through `output` until the
+    /// line is valid.
+    ///
+    /// `prompt` is written and flushed before each read, and the parse error is
+    /// written before each re-prompt. Re-prompting stops with
+    /// `FrameworkError::EndOfInput` when there is no more input.
+    fn read_parsed_reprompt<T, O>(
+        &mut self,
+        output: &mut O,
+        prompt: &str,
+    ) -> Result<T, FrameworkError>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+        O: Output + ?Sized,
+    {
+        reprompt_parsed(output, prompt, |_| self.read())
+    }
+}
+
+impl<I> InputExt for I where I: Input + ?Sized {}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Prompts for and parses a line as `T`, re-prompting until the line is
+    /// valid.
+    ///
+    /// Returns `FrameworkError::Interrupted` if the context's cancel token is
+    /// cancelled while waiting for input. Each read is within the read
+    /// timeout, as with `CmdCtx::read_line`.
+    pub fn prompt_parsed<T>(&mut self, prompt: &str) -> Result<T, FrameworkError>
+    where
+        T: FromStr,
+        T::Err: std::error::Error + Send + Sync + 'static,
+    {
+        let CmdCtx {
+            input,
+            output,
+            logger,
+            cancel_token,
+            timeouts,
+            ..
+        } = self;
+
+        logger.in_span("read", |logger| {
+            reprompt_parsed(output, prompt, |output| {
+                timeout::read_line(input, output, logger, cancel_token, timeouts)
+            })
+        })
+    }
+}
+
+/// Writes `prompt` and parses the line from `read` as `T`, re-prompting until
+/// the line is valid.
+fn reprompt_parsed<T, O, F>(output: &mut O, prompt: &str, mut read: F) -> Result<T, FrameworkError>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+    O: Output + ?Sized,
+    F: FnMut(&mut O) -> Result<String, FrameworkError>,
+{
+    loop {
+        output.write(prompt)?;
+        output.flush()?;
+
+        let line = read(output)?;
+        match parse_line(&line) {
+            Ok(t) => return Ok(t),
+            Err(FrameworkError::Parse { input, error }) => {
+                output.write(&format!("Invalid input `{input}`: {error}\n"))?;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Parses a line as `T`, without its trailing newline.
+fn parse_line<T>(line: &str) -> Result<T, FrameworkError>
+where
+    T: FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let text = line.trim_end_matches(['\r', '\n']);
+
+    text.parse::<T>().map_err(|error| FrameworkError::Parse {
+        input: text.to_string(),
+        error: Box::new(error),
+    })
+}
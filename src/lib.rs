@@ -0,0 +1,858 @@
+//! Experiment using single type with associated types to track type params.
+
+// Allows `#[derive(TypeParams)]` to be used within this crate, as the generated
+// code refers to `::assoc_type_params`.
+extern crate self as assoc_type_params;
+
+pub use assoc_type_params_derive::TypeParams;
+
+pub use crate::args::{ArgMatches, ArgSpec, ArgsEndpoint, ArgsInput, FromArgs};
+pub use crate::asynchronous::{
+    AsyncAdapter, AsyncInput, AsyncLogic, AsyncOutput, AsyncStdioEndpoint,
+    AsyncTypeParamsConstrained, BlockingAdapter, run_async,
+};
+pub use crate::cancel::CancelToken;
+pub use crate::commands::{Commands, Dispatcher};
+pub use crate::exit_code::{ToExitCode, run_main, sysexits};
+pub use crate::file::{FileEndpoint, FileInput, FileOutput, FileOutputMode};
+pub use crate::input_ext::InputExt;
+#[cfg(feature = "tracing")]
+pub use crate::log::TracingLog;
+pub use crate::log::{DiscardLog, FileLog, Level, Log, StderrLog};
+pub use crate::logic_ext::{AndThen, ErrInto, LogicExt, Map, MapErr, Then};
+pub use crate::memory::{
+    MemoryEndpoint, MemoryInput, MemoryOutput, ScriptedEndpoint, ScriptedInput,
+};
+pub use crate::present::{OutputExt, OutputFormat, ParseOutputFormatError, Presentable};
+pub use crate::progress::{Progress, ProgressStyle};
+pub use crate::prompt::Validator;
+pub use crate::repl::Repl;
+pub use crate::report::{Diagnostic, ErrorReport};
+pub use crate::resources::{ResourceError, Resources};
+pub use crate::retry::{Attempt, Retry, RetryError};
+pub use crate::secret::{Secret, SecretInput};
+pub use crate::timeout::{TimeoutPhase, Timeouts};
+
+mod args;
+mod asynchronous;
+mod cancel;
+mod commands;
+mod exit_code;
+mod file;
+mod input_ext;
+mod log;
+mod logic_ext;
+mod memory;
+#[cfg(unix)]
+mod poll;
+mod present;
+mod progress;
+mod prompt;
+mod repl;
+mod report;
+mod resources;
+mod retry;
+mod secret;
+mod timeout;
+
+use std::{
+    fmt::{self, Display},
+    io::{IsTerminal, Stdin, Stdout, Write},
+    marker::PhantomData,
+    str::FromStr,
+    time::Instant,
+};
+
+// === Traits for pluggable types, with compile time safety / static checking.
+// === //
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as the `Input` type",
+    label = "this type does not implement `Input`"
+)]
+pub trait Input {
+    /// Reads the next line, including its trailing newline if there is one.
+    ///
+    /// Returns `FrameworkError::EndOfInput` when there is no more input, so an
+    /// empty line is never confused with the end of input.
+    fn read(&mut self) -> Result<String, FrameworkError>;
+
+    /// Reads the next line, returning the error from `cancel_token.check()` if
+    /// the token is cancelled or its deadline passes.
+    ///
+    /// The default implementation checks the token before and after `read`,
+    /// so it only stops early if `read` doesn't block.
+    fn read_cancellable(&mut self, cancel_token: &CancelToken) -> Result<String, FrameworkError> {
+        cancel_token.check()?;
+        let line = self.read()?;
+        cancel_token.check()?;
+
+        Ok(line)
+    }
+}
+
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as the `Output` type",
+    label = "this type does not implement `Output`"
+)]
+pub trait Output {
+    fn write(&mut self, s: &str) -> Result<(), FrameworkError>;
+
+    /// Writes an error report, e.g. when the command fails.
+    ///
+    /// The default implementation writes to the output. Outputs that hold data,
+    /// or whose writes are discarded when aborted, write the report elsewhere so
+    /// that it is seen.
+    fn write_error(&mut self, s: &str) -> Result<(), FrameworkError> {
+        self.write(s)
+    }
+
+    /// Flushes buffered writes, e.g. so that a line being redrawn is shown.
+    fn flush(&mut self) -> Result<(), FrameworkError> {
+        Ok(())
+    }
+
+    /// Returns whether the output is an interactive terminal.
+    fn is_terminal(&self) -> bool {
+        false
+    }
+
+    /// Called when the context finishes, to flush or commit what was written.
+    fn finish(&mut self) -> Result<(), FrameworkError> {
+        Ok(())
+    }
+
+    /// Called instead of `finish` when the command fails, to flush what was
+    /// written without committing it.
+    ///
+    /// The default implementation flushes.
+    fn abort(&mut self) -> Result<(), FrameworkError> {
+        self.flush()
+    }
+}
+
+pub trait Logic {
+    type ReturnType;
+    type Error: std::error::Error;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error>;
+}
+
+/// Logic that is given the `CmdCtx`, so it can do its own input and output.
+///
+/// The `Input` value is read and parsed before the logic is run, so the logic
+/// receives typed input.
+pub trait CtxLogic<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    type Input;
+    type ReturnType;
+    type Error: std::error::Error;
+
+    fn do_work(
+        &mut self,
+        cmd_ctx: &mut CmdCtx<Types>,
+        input: Self::Input,
+    ) -> Result<Self::ReturnType, Self::Error>;
+}
+
+/// Trait that tracks all associated types;
+///
+/// This can be derived with `#[derive(TypeParams)]`.
+pub trait TypeParamsT {
+    type AppError;
+    type Input;
+    type Output;
+    /// Shared state that logic can reach through `CmdCtx::state()`, e.g.
+    /// configuration, caches, or database handles.
+    type State;
+    /// Records diagnostics, separately from the output.
+    type Logger;
+}
+
+/// Similar to `TypeParamsT`, with bounds added for compile time safety.
+///
+/// The associated types of `TypeParamsT` must be specified, otherwise we
+/// haven't told Rust that the supertrait's associated type must be exactly the
+/// same as this trait's associated types.
+///
+/// Much thanks to `@quinedot`.
+///
+/// See <https://users.rust-lang.org/t/trait-bounds-transitive-inference/105118>
+pub trait TypeParamsConstrained:
+    TypeParamsT<
+        AppError = <Self as TypeParamsConstrained>::AppError,
+        Input = <Self as TypeParamsConstrained>::Input,
+        Output = <Self as TypeParamsConstrained>::Output,
+        State = <Self as TypeParamsConstrained>::State,
+        Logger = <Self as TypeParamsConstrained>::Logger,
+    >
+{
+    type AppError: std::error::Error + 'static;
+    type Input: Input + 'static;
+    type Output: Output + 'static;
+    type State: 'static;
+    type Logger: Log + 'static;
+}
+
+impl<T> TypeParamsConstrained for T
+where
+    T: TypeParamsT,
+    T::AppError: std::error::Error + 'static,
+    T::Input: Input + 'static,
+    T::Output: Output + 'static,
+    T::State: 'static,
+    T::Logger: Log + 'static,
+{
+    type AppError = T::AppError;
+    type Input = T::Input;
+    type Output = T::Output;
+    type State = T::State;
+    type Logger = T::Logger;
+}
+
+// === Error / Value types === //
+
+#[derive(Debug)]
+pub struct LogicError(pub String);
+
+impl std::error::Error for LogicError {}
+
+impl Display for LogicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Debug)]
+pub enum FrameworkError {
+    Logic(LogicError),
+    Input(std::io::Error),
+    Output(std::io::Error),
+    /// There is no more input to read.
+    EndOfInput,
+    /// The command was used incorrectly, with the usage text.
+    Usage(String),
+    /// A value could not be serialized for presentation.
+    Present(Box<dyn std::error::Error + Send + Sync + 'static>),
+    /// Input could not be parsed into the requested type.
+    Parse {
+        /// The text that was entered, without its trailing newline.
+        input: String,
+        /// The error from parsing the text.
+        error: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    /// A resource could not be borrowed from the `CmdCtx`.
+    Resource(ResourceError),
+    /// The command was cancelled, e.g. by Ctrl-C.
+    Interrupted,
+    /// A phase of the command did not complete within its timeout.
+    Timeout(TimeoutPhase),
+    /// Logic failed on every attempt made by `Retry`.
+    Retry {
+        /// The history of attempts, one line per attempt.
+        history: String,
+        /// The `RetryError`.
+        error: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+impl From<LogicError> for FrameworkError {
+    fn from(error: LogicError) -> Self {
+        Self::Logic(error)
+    }
+}
+
+impl std::error::Error for FrameworkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FrameworkError::Logic(error) => Some(error),
+            FrameworkError::Input(error) => Some(error),
+            FrameworkError::Output(error) => Some(error),
+            FrameworkError::EndOfInput => None,
+            FrameworkError::Usage(_) => None,
+            FrameworkError::Present(error) => Some(error.as_ref()),
+            FrameworkError::Parse { input: _, error } => Some(error.as_ref()),
+            FrameworkError::Resource(error) => Some(error),
+            FrameworkError::Interrupted => None,
+            FrameworkError::Timeout(_) => None,
+            FrameworkError::Retry { history: _, error } => Some(error.as_ref()),
+        }
+    }
+
+    fn cause(&self) -> Option<&dyn std::error::Error> {
+        self.source()
+    }
+}
+
+impl Display for FrameworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameworkError::Logic(_) => write!(f, "Logic error"),
+            FrameworkError::Input(_) => write!(f, "Input error"),
+            FrameworkError::Output(_) => write!(f, "Output error"),
+            FrameworkError::EndOfInput => write!(f, "End of input"),
+            FrameworkError::Usage(usage) => usage.fmt(f),
+            FrameworkError::Present(_) => write!(f, "Present error"),
+            FrameworkError::Parse { input, error: _ } => write!(f, "Parse error: `{input}`"),
+            FrameworkError::Resource(_) => write!(f, "Resource error"),
+            FrameworkError::Interrupted => write!(f, "Interrupted"),
+            FrameworkError::Timeout(phase) => write!(f, "Timed out during {phase}"),
+            FrameworkError::Retry {
+                history: _,
+                error: _,
+            } => write!(f, "Retry error"),
+        }
+    }
+}
+
+// === Type params markers === //
+
+/// Type params marker for any combination of error, input, output, state, and
+/// logger types.
+///
+/// This saves writing a marker struct for each combination, e.g.
+/// `CmdCtx<TypeParams<FrameworkError, Stdin, Stdout>>`. The state defaults to
+/// `()`, and the logger defaults to `DiscardLog`.
+///
+/// This is never instantiated, it is only used to track the types.
+pub struct TypeParams<AppError, In, Out, State = (), Logger = DiscardLog>(
+    PhantomData<(AppError, In, Out, State, Logger)>,
+);
+
+impl<AppError, In, Out, State, Logger> TypeParamsT
+    for TypeParams<AppError, In, Out, State, Logger>
+{
+    type AppError = AppError;
+    type Input = In;
+    type Output = Out;
+    type State = State;
+    type Logger = Logger;
+}
+
+// === Context capturing types === //
+
+pub struct CmdCtx<Types>
+where
+    Types: TypeParamsT,
+{
+    pub input: Types::Input,
+    pub output: Types::Output,
+    /// Records diagnostics, separately from the output.
+    pub logger: Types::Logger,
+    /// Format that values are presented in.
+    pub output_format: OutputFormat,
+    /// Shared state for logic.
+    state: Types::State,
+    /// Resources that logic can insert and look up by type.
+    resources: Resources,
+    /// Cancelled when the command should stop.
+    cancel_token: CancelToken,
+    /// Timeouts for each phase of the command.
+    timeouts: Timeouts,
+}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsT,
+{
+    /// Returns a reference to the shared state.
+    pub fn state(&self) -> &Types::State {
+        &self.state
+    }
+
+    /// Returns a mutable reference to the shared state.
+    pub fn state_mut(&mut self) -> &mut Types::State {
+        &mut self.state
+    }
+}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Finishes the context, flushing or committing what was written to the
+    /// output.
+    pub fn finish(&mut self) -> Result<(), FrameworkError> {
+        let CmdCtx { output, logger, .. } = self;
+
+        logger.in_span("finish", |_| output.finish())
+    }
+
+    /// Aborts the context when the command fails, flushing what was written to
+    /// the output without committing it.
+    pub fn abort(&mut self) -> Result<(), FrameworkError> {
+        let CmdCtx { output, logger, .. } = self;
+
+        logger.in_span("abort", |_| output.abort())
+    }
+}
+
+// === Builder === //
+
+/// Placeholder for a type that has not been set on the `CmdCtxBuilder`.
+///
+/// This does not implement `Input`, `Output`, or `std::error::Error`, so a
+/// `CmdCtxBuilder` with any `Unset` types cannot be built.
+pub struct Unset;
+
+/// Builds a `CmdCtx`, tracking each type param as it is set.
+///
+/// `build()` is only available once the input, output, and error types are all
+/// set, and they satisfy the bounds of `TypeParamsConstrained`.
+pub struct CmdCtxBuilder<Types>
+where
+    Types: TypeParamsT,
+{
+    input: Types::Input,
+    output: Types::Output,
+    logger: Types::Logger,
+    output_format: OutputFormat,
+    state: Types::State,
+    resources: Resources,
+    cancel_token: CancelToken,
+    timeouts: Timeouts,
+}
+
+impl CmdCtxBuilder<TypeParams<Unset, Unset, Unset>> {
+    /// Returns a new `CmdCtxBuilder` with no types set.
+    pub fn new() -> Self {
+        Self {
+            input: Unset,
+            output: Unset,
+            logger: DiscardLog,
+            output_format: OutputFormat::default(),
+            state: (),
+            resources: Resources::new(),
+            cancel_token: CancelToken::new(),
+            timeouts: Timeouts::new(),
+        }
+    }
+}
+
+impl Default for CmdCtxBuilder<TypeParams<Unset, Unset, Unset>> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<AppError, In, Out, State, Logger> CmdCtxBuilder<TypeParams<AppError, In, Out, State, Logger>> {
+    /// Sets the input for the `CmdCtx`.
+    pub fn with_input<InNext>(
+        self,
+        input: InNext,
+    ) -> CmdCtxBuilder<TypeParams<AppError, InNext, Out, State, Logger>> {
+        let CmdCtxBuilder {
+            input: _,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        } = self;
+
+        CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        }
+    }
+
+    /// Sets the output for the `CmdCtx`.
+    pub fn with_output<OutNext>(
+        self,
+        output: OutNext,
+    ) -> CmdCtxBuilder<TypeParams<AppError, In, OutNext, State, Logger>> {
+        let CmdCtxBuilder {
+            input,
+            output: _,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        } = self;
+
+        CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        }
+    }
+
+    /// Sets the application error type for the `CmdCtx`.
+    pub fn with_error<AppErrorNext>(
+        self,
+    ) -> CmdCtxBuilder<TypeParams<AppErrorNext, In, Out, State, Logger>> {
+        let CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        } = self;
+
+        CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        }
+    }
+
+    /// Sets the shared state for the `CmdCtx`.
+    pub fn with_state<StateNext>(
+        self,
+        state: StateNext,
+    ) -> CmdCtxBuilder<TypeParams<AppError, In, Out, StateNext, Logger>> {
+        let CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state: _,
+            resources,
+            cancel_token,
+            timeouts,
+        } = self;
+
+        CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        }
+    }
+
+    /// Sets the logger for the `CmdCtx`.
+    pub fn with_logger<LoggerNext>(
+        self,
+        logger: LoggerNext,
+    ) -> CmdCtxBuilder<TypeParams<AppError, In, Out, State, LoggerNext>> {
+        let CmdCtxBuilder {
+            input,
+            output,
+            logger: _,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        } = self;
+
+        CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        }
+    }
+
+    /// Returns the `CmdCtx` for a marker with the same types, such as one that
+    /// is declared with `#[derive(TypeParams)]`.
+    pub fn build_as<Types>(self) -> CmdCtx<Types>
+    where
+        Types: TypeParamsConstrained<
+                AppError = AppError,
+                Input = In,
+                Output = Out,
+                State = State,
+                Logger = Logger,
+            >,
+    {
+        let CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        } = self;
+
+        CmdCtx {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        }
+    }
+}
+
+impl<Types> CmdCtxBuilder<Types>
+where
+    Types: TypeParamsT,
+{
+    /// Sets the format that values are presented in.
+    pub fn with_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+}
+
+impl<Types> CmdCtxBuilder<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Returns the `CmdCtx` with all of its types set.
+    pub fn build(self) -> CmdCtx<Types> {
+        let CmdCtxBuilder {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        } = self;
+
+        CmdCtx {
+            input,
+            output,
+            logger,
+            output_format,
+            state,
+            resources,
+            cancel_token,
+            timeouts,
+        }
+    }
+}
+
+// === Concrete implementations of pluggable types === //
+
+impl Input for Stdin {
+    fn read(&mut self) -> Result<String, FrameworkError> {
+        let mut buffer = String::with_capacity(256);
+        let n = self.read_line(&mut buffer).map_err(FrameworkError::Input)?;
+
+        if n == 0 {
+            Err(FrameworkError::EndOfInput)
+        } else {
+            Ok(buffer)
+        }
+    }
+
+    /// On a terminal on unix, waits for a line while checking `cancel_token`,
+    /// so that Ctrl-C and the read timeout stop the read.
+    ///
+    /// Otherwise the read blocks until a line arrives, and the token is only
+    /// checked before and after.
+    fn read_cancellable(&mut self, cancel_token: &CancelToken) -> Result<String, FrameworkError> {
+        // Piped input is read without waiting, as lines that are already
+        // buffered by `Stdin` are not seen by `poll`.
+        #[cfg(unix)]
+        if IsTerminal::is_terminal(self) {
+            poll::wait_readable(std::os::fd::AsRawFd::as_raw_fd(self), cancel_token)?;
+        }
+
+        cancel_token.check()?;
+        let line = self.read()?;
+        cancel_token.check()?;
+
+        Ok(line)
+    }
+}
+
+impl Output for Stdout {
+    fn write(&mut self, s: &str) -> Result<(), FrameworkError> {
+        self.lock()
+            .write_all(s.as_bytes())
+            .map_err(FrameworkError::Output)
+    }
+
+    fn flush(&mut self) -> Result<(), FrameworkError> {
+        self.lock().flush().map_err(FrameworkError::Output)
+    }
+
+    fn is_terminal(&self) -> bool {
+        IsTerminal::is_terminal(self)
+    }
+
+    fn finish(&mut self) -> Result<(), FrameworkError> {
+        Output::flush(self)
+    }
+}
+
+#[derive(TypeParams)]
+#[type_params(error = FrameworkError, input = Stdin, output = Stdout)]
+pub struct StdioEndpoint;
+
+// === User level logic === //
+
+/// Prompts for a line of input, runs `logic`, and writes the line back.
+///
+/// The cancel token is checked between steps. The work timeout is advisory,
+/// as `Logic::do_work` has no access to the cancel token: logic that takes
+/// longer than its timeout runs to completion, its result is kept, and a
+/// warning is logged.
+pub fn run<Types, L>(
+    cmd_ctx: &mut CmdCtx<Types>,
+    logic: &mut L,
+) -> Result<L::ReturnType, <Types as TypeParamsConstrained>::AppError>
+where
+    Types: TypeParamsConstrained,
+    L: Logic,
+    <Types as TypeParamsConstrained>::AppError: From<L::Error> + From<FrameworkError>,
+    //
+    // These bounds don't have to be specified individually, since Rust can infer them from
+    // `TypeParamsConstrained`.
+    //
+    // <Types as TypeParamsT>::Output: Output,
+    // <Types as TypeParamsT>::Input: Input,
+{
+    let CmdCtx {
+        input,
+        output,
+        logger,
+        cancel_token,
+        timeouts,
+        ..
+    } = cmd_ctx;
+
+    let result = logger.in_span("run", |logger| {
+        logger.in_span("write", |_| output.write("Enter some input:\n"))?;
+
+        let line = logger.in_span("read", |logger| {
+            timeout::read_line(input, output, logger, cancel_token, timeouts)
+        })?;
+        logger.event(Level::Debug, &format!("read {} bytes", line.len()));
+
+        cancel_token.check()?;
+        let t = logger.in_span("work", |logger| {
+            let start = Instant::now();
+            let result =
+                cancel_token.with_timeout(TimeoutPhase::Work, timeouts.work(), || logic.do_work());
+            timeout::warn_overrun(logger, timeouts.work(), start.elapsed());
+
+            result.map_err(|error| {
+                logger.event(Level::Error, &format!("logic failed: {error}"));
+                <Types as TypeParamsConstrained>::AppError::from(error)
+            })
+        })?;
+
+        cancel_token.check()?;
+        logger.in_span("write", |_| {
+            output.write("You entered: ")?;
+            output.write(&line)
+        })?;
+
+        Ok(t)
+    });
+
+    flush_then(output, result)
+}
+
+/// Like `run`, but for logic that is given the `CmdCtx` and typed input.
+///
+/// The input is re-prompted for until it can be parsed as `L::Input`. The
+/// logic can stop at the work timeout by checking `cmd_ctx.cancel_token()`.
+pub fn run_ctx<Types, L>(
+    cmd_ctx: &mut CmdCtx<Types>,
+    logic: &mut L,
+) -> Result<L::ReturnType, <Types as TypeParamsConstrained>::AppError>
+where
+    Types: TypeParamsConstrained,
+    L: CtxLogic<Types>,
+    L::Input: FromStr,
+    <L::Input as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    <Types as TypeParamsConstrained>::AppError: From<L::Error> + From<FrameworkError>,
+{
+    cmd_ctx.logger.span_enter("run");
+    let result = run_ctx_steps(cmd_ctx, logic);
+    cmd_ctx.logger.span_exit("run");
+
+    flush_then(&mut cmd_ctx.output, result)
+}
+
+/// Flushes `output`, then returns `result`.
+///
+/// If `result` is an error, it is returned even if flushing fails, as it is
+/// the cause of stopping.
+fn flush_then<O, T, E>(output: &mut O, result: Result<T, E>) -> Result<T, E>
+where
+    O: Output,
+    E: From<FrameworkError>,
+{
+    match result {
+        Ok(t) => {
+            output.flush()?;
+            Ok(t)
+        }
+        Err(error) => {
+            let _ = output.flush();
+            Err(error)
+        }
+    }
+}
+
+fn run_ctx_steps<Types, L>(
+    cmd_ctx: &mut CmdCtx<Types>,
+    logic: &mut L,
+) -> Result<L::ReturnType, <Types as TypeParamsConstrained>::AppError>
+where
+    Types: TypeParamsConstrained,
+    L: CtxLogic<Types>,
+    L::Input: FromStr,
+    <L::Input as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    <Types as TypeParamsConstrained>::AppError: From<L::Error> + From<FrameworkError>,
+{
+    let input = cmd_ctx.prompt_parsed::<L::Input>("Enter some input:\n")?;
+    cmd_ctx.cancel_token.check()?;
+
+    // The logic is given the whole `CmdCtx`, so the span is entered and exited
+    // around it rather than borrowing the logger.
+    let cancel_token = cmd_ctx.cancel_token.clone();
+    let work_timeout = cmd_ctx.timeouts.work();
+    cmd_ctx.logger.span_enter("work");
+    let start = Instant::now();
+    let result = cancel_token
+        .with_timeout(TimeoutPhase::Work, work_timeout, || {
+            logic.do_work(cmd_ctx, input)
+        })
+        .map_err(<Types as TypeParamsConstrained>::AppError::from);
+    timeout::warn_overrun(&mut cmd_ctx.logger, work_timeout, start.elapsed());
+    if let Err(error) = &result {
+        cmd_ctx
+            .logger
+            .event(Level::Error, &format!("logic failed: {error}"));
+    }
+    cmd_ctx.logger.span_exit("work");
+
+    result
+}
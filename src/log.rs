@@ -0,0 +1,231 @@
+use std::{
+    fmt::{self, Display},
+    fs::{File, OpenOptions},
+    io::{LineWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::FrameworkError;
+
+/// Severity of a log event, from most to least severe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Error => "ERROR".fmt(f),
+            Level::Warn => "WARN".fmt(f),
+            Level::Info => "INFO".fmt(f),
+            Level::Debug => "DEBUG".fmt(f),
+            Level::Trace => "TRACE".fmt(f),
+        }
+    }
+}
+
+/// Records diagnostics, separately from the user facing `Output`.
+///
+/// Logging never fails the command, so implementations discard events that
+/// cannot be recorded.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be used as the `Logger` type",
+    label = "this type does not implement `Log`"
+)]
+pub trait Log {
+    /// Records an event in the current span.
+    fn event(&mut self, level: Level, message: &str);
+
+    /// Enters a span, which events are recorded in until it is exited.
+    fn span_enter(&mut self, name: &'static str);
+
+    /// Exits the most recently entered span.
+    fn span_exit(&mut self, name: &'static str);
+
+    /// Runs `f` within the span `name`.
+    fn in_span<T, F>(&mut self, name: &'static str, f: F) -> T
+    where
+        Self: Sized,
+        F: FnOnce(&mut Self) -> T,
+    {
+        self.span_enter(name);
+        let t = f(self);
+        self.span_exit(name);
+
+        t
+    }
+}
+
+/// `Log` that discards every event.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiscardLog;
+
+impl Log for DiscardLog {
+    fn event(&mut self, _level: Level, _message: &str) {}
+
+    fn span_enter(&mut self, _name: &'static str) {}
+
+    fn span_exit(&mut self, _name: &'static str) {}
+}
+
+/// `Log` that writes events to stderr.
+///
+/// Each event is written on its own line, prefixed with its level and the
+/// spans it is in, e.g. `INFO run:read: read 4 bytes`.
+#[derive(Clone, Debug)]
+pub struct StderrLog {
+    /// Least severe level that is written.
+    level: Level,
+    /// Names of the entered spans, outermost first.
+    spans: Vec<&'static str>,
+}
+
+impl StderrLog {
+    /// Returns a new `StderrLog` that writes events at `level` and more
+    /// severe levels.
+    pub fn new(level: Level) -> Self {
+        Self {
+            level,
+            spans: Vec::new(),
+        }
+    }
+}
+
+impl Default for StderrLog {
+    fn default() -> Self {
+        Self::new(Level::Info)
+    }
+}
+
+impl Log for StderrLog {
+    fn event(&mut self, level: Level, message: &str) {
+        if level <= self.level {
+            write_event(&mut std::io::stderr().lock(), level, &self.spans, message);
+        }
+    }
+
+    fn span_enter(&mut self, name: &'static str) {
+        self.spans.push(name);
+    }
+
+    fn span_exit(&mut self, _name: &'static str) {
+        self.spans.pop();
+    }
+}
+
+/// `Log` that appends events to a file.
+///
+/// Events are written in the same format as `StderrLog`.
+#[derive(Debug)]
+pub struct FileLog {
+    /// Path to the file.
+    path: PathBuf,
+    /// Least severe level that is written.
+    level: Level,
+    /// Names of the entered spans, outermost first.
+    spans: Vec<&'static str>,
+    /// Writer over the file, which is flushed after each event.
+    writer: LineWriter<File>,
+}
+
+impl FileLog {
+    /// Opens the file at `path` for appending events at `level` and more
+    /// severe levels, creating it if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>, level: Level) -> Result<Self, FrameworkError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(&path)
+            .map_err(FrameworkError::Output)?;
+
+        Ok(Self {
+            path,
+            level,
+            spans: Vec::new(),
+            writer: LineWriter::new(file),
+        })
+    }
+
+    /// Returns the path to the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Log for FileLog {
+    fn event(&mut self, level: Level, message: &str) {
+        if level <= self.level {
+            write_event(&mut self.writer, level, &self.spans, message);
+        }
+    }
+
+    fn span_enter(&mut self, name: &'static str) {
+        self.spans.push(name);
+    }
+
+    fn span_exit(&mut self, _name: &'static str) {
+        self.spans.pop();
+    }
+}
+
+/// `Log` that forwards spans and events to the `tracing` crate.
+///
+/// Each span is a `tracing` span named `step`, with the span name recorded in
+/// its `name` field.
+#[cfg(feature = "tracing")]
+#[derive(Debug, Default)]
+pub struct TracingLog {
+    /// Entered spans, outermost first.
+    spans: Vec<tracing::span::EnteredSpan>,
+}
+
+#[cfg(feature = "tracing")]
+impl TracingLog {
+    /// Returns a new `TracingLog`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Log for TracingLog {
+    fn event(&mut self, level: Level, message: &str) {
+        match level {
+            Level::Error => tracing::error!("{message}"),
+            Level::Warn => tracing::warn!("{message}"),
+            Level::Info => tracing::info!("{message}"),
+            Level::Debug => tracing::debug!("{message}"),
+            Level::Trace => tracing::trace!("{message}"),
+        }
+    }
+
+    fn span_enter(&mut self, name: &'static str) {
+        self.spans.push(tracing::info_span!("step", name).entered());
+    }
+
+    fn span_exit(&mut self, _name: &'static str) {
+        // Dropping the span exits it.
+        self.spans.pop();
+    }
+}
+
+/// Writes an event as a line, prefixed with its level and spans.
+fn write_event<W>(writer: &mut W, level: Level, spans: &[&'static str], message: &str)
+where
+    W: Write,
+{
+    let result = if spans.is_empty() {
+        writeln!(writer, "{level} {message}")
+    } else {
+        writeln!(writer, "{level} {}: {message}", spans.join(":"))
+    };
+
+    // Logging never fails the command.
+    let _ = result;
+}
@@ -0,0 +1,171 @@
+use std::marker::PhantomData;
+
+use crate::{Logic, Retry};
+
+/// Combinators that compose `Logic` into new `Logic`.
+pub trait LogicExt: Logic + Sized {
+    /// Runs `self`, then `next`, returning `next`'s return value.
+    ///
+    /// `self`'s error is converted into `next`'s error through `From`. Use
+    /// `err_into` on both when neither error converts into the other.
+    fn then<B>(self, next: B) -> Then<Self, B>
+    where
+        B: Logic,
+        B::Error: From<Self::Error>,
+    {
+        Then { first: self, next }
+    }
+
+    /// Maps the return value with `f`.
+    fn map<F, R>(self, f: F) -> Map<Self, F>
+    where
+        F: FnMut(Self::ReturnType) -> R,
+    {
+        Map { logic: self, f }
+    }
+
+    /// Maps the error with `f`.
+    fn map_err<F, E>(self, f: F) -> MapErr<Self, F>
+    where
+        F: FnMut(Self::Error) -> E,
+        E: std::error::Error,
+    {
+        MapErr { logic: self, f }
+    }
+
+    /// Runs `self`, then the `Logic` that `f` returns for `self`'s return value.
+    ///
+    /// `self`'s error is converted into the next logic's error through `From`.
+    fn and_then<F, B>(self, f: F) -> AndThen<Self, F>
+    where
+        F: FnMut(Self::ReturnType) -> B,
+        B: Logic,
+        B::Error: From<Self::Error>,
+    {
+        AndThen { logic: self, f }
+    }
+
+    /// Runs `self` again when it fails, see `Retry`.
+    fn retry(self) -> Retry<Self, fn(&Self::Error) -> bool> {
+        Retry::new(self)
+    }
+
+    /// Converts the error into `E`, such as the application's `AppError`.
+    fn err_into<E>(self) -> ErrInto<Self, E>
+    where
+        E: std::error::Error + From<Self::Error>,
+    {
+        ErrInto {
+            logic: self,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<L> LogicExt for L where L: Logic {}
+
+/// `Logic` returned by `LogicExt::then`.
+#[derive(Clone, Debug)]
+pub struct Then<A, B> {
+    first: A,
+    next: B,
+}
+
+impl<A, B> Logic for Then<A, B>
+where
+    A: Logic,
+    B: Logic,
+    B::Error: From<A::Error>,
+{
+    type Error = B::Error;
+    type ReturnType = B::ReturnType;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        let _ = self.first.do_work()?;
+        self.next.do_work()
+    }
+}
+
+/// `Logic` returned by `LogicExt::map`.
+#[derive(Clone, Debug)]
+pub struct Map<L, F> {
+    logic: L,
+    f: F,
+}
+
+impl<L, F, R> Logic for Map<L, F>
+where
+    L: Logic,
+    F: FnMut(L::ReturnType) -> R,
+{
+    type Error = L::Error;
+    type ReturnType = R;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        self.logic.do_work().map(&mut self.f)
+    }
+}
+
+/// `Logic` returned by `LogicExt::map_err`.
+#[derive(Clone, Debug)]
+pub struct MapErr<L, F> {
+    logic: L,
+    f: F,
+}
+
+impl<L, F, E> Logic for MapErr<L, F>
+where
+    L: Logic,
+    F: FnMut(L::Error) -> E,
+    E: std::error::Error,
+{
+    type Error = E;
+    type ReturnType = L::ReturnType;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        self.logic.do_work().map_err(&mut self.f)
+    }
+}
+
+/// `Logic` returned by `LogicExt::and_then`.
+#[derive(Clone, Debug)]
+pub struct AndThen<L, F> {
+    logic: L,
+    f: F,
+}
+
+impl<L, F, B> Logic for AndThen<L, F>
+where
+    L: Logic,
+    F: FnMut(L::ReturnType) -> B,
+    B: Logic,
+    B::Error: From<L::Error>,
+{
+    type Error = B::Error;
+    type ReturnType = B::ReturnType;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        let t = self.logic.do_work()?;
+        (self.f)(t).do_work()
+    }
+}
+
+/// `Logic` returned by `LogicExt::err_into`.
+#[derive(Debug)]
+pub struct ErrInto<L, E> {
+    logic: L,
+    marker: PhantomData<fn() -> E>,
+}
+
+impl<L, E> Logic for ErrInto<L, E>
+where
+    L: Logic,
+    E: std::error::Error + From<L::Error>,
+{
+    type Error = E;
+    type ReturnType = L::ReturnType;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        self.logic.do_work().map_err(E::from)
+    }
+}
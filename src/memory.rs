@@ -0,0 +1,188 @@
+use std::{collections::VecDeque, time::Duration};
+
+use crate::{CancelToken, FrameworkError, Input, Output, TypeParams};
+
+/// How long a `ScriptedInput` sleeps between checks of the cancel token.
+const SCRIPTED_CHECK_INTERVAL: Duration = Duration::from_millis(10);
+
+/// `Input` that reads lines from a scripted queue.
+///
+/// Each line is returned with a trailing newline, as if it were entered in a
+/// terminal. Once every line is read, `FrameworkError::EndOfInput` is returned.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryInput {
+    /// Lines that have not yet been read.
+    lines: VecDeque<String>,
+}
+
+impl MemoryInput {
+    /// Returns a new `MemoryInput` that reads the given lines in order.
+    pub fn new<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let lines = lines.into_iter().map(Into::into).collect();
+        Self { lines }
+    }
+
+    /// Adds a line to the end of the script.
+    pub fn push(&mut self, line: impl Into<String>) {
+        self.lines.push_back(line.into());
+    }
+
+    /// Returns the lines that have not yet been read.
+    pub fn remaining(&self) -> &VecDeque<String> {
+        &self.lines
+    }
+}
+
+impl Input for MemoryInput {
+    fn read(&mut self) -> Result<String, FrameworkError> {
+        let Some(mut line) = self.lines.pop_front() else {
+            return Err(FrameworkError::EndOfInput);
+        };
+        if !line.ends_with('\n') {
+            line.push('\n');
+        }
+
+        Ok(line)
+    }
+}
+
+/// `Input` that reads lines from a scripted queue, each after a delay.
+///
+/// This simulates a user or upstream process that is slow to respond, e.g. to
+/// exercise read timeouts. A read that is stopped by the cancel token keeps
+/// the line, with the time already waited taken off its delay.
+#[derive(Clone, Debug, Default)]
+pub struct ScriptedInput {
+    /// Lines that have not yet been read, with how long each takes to arrive.
+    lines: VecDeque<(Duration, String)>,
+}
+
+impl ScriptedInput {
+    /// Returns a new `ScriptedInput` that reads the given lines in order, each
+    /// after its delay.
+    pub fn new<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = (Duration, S)>,
+        S: Into<String>,
+    {
+        let lines = lines
+            .into_iter()
+            .map(|(delay, line)| (delay, line.into()))
+            .collect();
+        Self { lines }
+    }
+
+    /// Adds a line to the end of the script, which arrives after `delay`.
+    pub fn push(&mut self, delay: Duration, line: impl Into<String>) {
+        self.lines.push_back((delay, line.into()));
+    }
+
+    /// Returns the lines that have not yet been read, with their delays.
+    pub fn remaining(&self) -> &VecDeque<(Duration, String)> {
+        &self.lines
+    }
+
+    fn pop_line(&mut self) -> Result<String, FrameworkError> {
+        let Some((_delay, mut line)) = self.lines.pop_front() else {
+            return Err(FrameworkError::EndOfInput);
+        };
+        if !line.ends_with('\n') {
+            line.push('\n');
+        }
+
+        Ok(line)
+    }
+}
+
+impl Input for ScriptedInput {
+    fn read(&mut self) -> Result<String, FrameworkError> {
+        if let Some((delay, _line)) = self.lines.front_mut() {
+            std::thread::sleep(std::mem::take(delay));
+        }
+
+        self.pop_line()
+    }
+
+    fn read_cancellable(&mut self, cancel_token: &CancelToken) -> Result<String, FrameworkError> {
+        loop {
+            cancel_token.check()?;
+
+            let Some((delay, _line)) = self.lines.front_mut() else {
+                return Err(FrameworkError::EndOfInput);
+            };
+            if delay.is_zero() {
+                return self.pop_line();
+            }
+
+            let interval = (*delay).min(SCRIPTED_CHECK_INTERVAL);
+            std::thread::sleep(interval);
+            *delay -= interval;
+        }
+    }
+}
+
+/// `Output` that records every `write` and `flush` call.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryOutput {
+    /// Each string passed to `write`, in order.
+    writes: Vec<String>,
+    /// Number of writes made before each `flush` call, in order.
+    flushes: Vec<usize>,
+}
+
+impl MemoryOutput {
+    /// Returns a new empty `MemoryOutput`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns each string passed to `write`, in order.
+    pub fn writes(&self) -> &[String] {
+        &self.writes
+    }
+
+    /// Returns the number of writes made before each `flush` call, in order.
+    ///
+    /// e.g. `[1]` means `flush` was called once, after the first write.
+    pub fn flushes(&self) -> &[usize] {
+        &self.flushes
+    }
+
+    /// Returns everything written, concatenated.
+    pub fn transcript(&self) -> String {
+        self.writes.concat()
+    }
+
+    /// Clears the recorded writes and flushes.
+    pub fn clear(&mut self) {
+        self.writes.clear();
+        self.flushes.clear();
+    }
+}
+
+impl Output for MemoryOutput {
+    fn write(&mut self, s: &str) -> Result<(), FrameworkError> {
+        self.writes.push(s.to_string());
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), FrameworkError> {
+        self.flushes.push(self.writes.len());
+        Ok(())
+    }
+}
+
+/// Type params marker for scripted input and recorded output, e.g. for tests.
+#[derive(TypeParams)]
+#[type_params(error = FrameworkError, input = MemoryInput, output = MemoryOutput)]
+pub struct MemoryEndpoint;
+
+/// Type params marker for delayed scripted input and recorded output, e.g. for
+/// testing timeouts.
+#[derive(TypeParams)]
+#[type_params(error = FrameworkError, input = ScriptedInput, output = MemoryOutput)]
+pub struct ScriptedEndpoint;
@@ -0,0 +1,46 @@
+use std::{io, os::fd::RawFd, time::Duration};
+
+use crate::{CancelToken, FrameworkError};
+
+/// How long to wait for input between checks of the cancel token.
+const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Waits until `fd` is readable, returning `FrameworkError::Interrupted` if
+/// `cancel_token` is cancelled first.
+pub(crate) fn wait_readable(fd: RawFd, cancel_token: &CancelToken) -> Result<(), FrameworkError> {
+    loop {
+        cancel_token.check()?;
+        if poll_readable(fd, CANCEL_CHECK_INTERVAL).map_err(FrameworkError::Input)? {
+            return Ok(());
+        }
+    }
+}
+
+/// Returns whether `fd` becomes readable within `timeout`.
+///
+/// A signal that interrupts the wait is treated as the timeout elapsing, so
+/// that the caller can check whether it should stop.
+pub(crate) fn poll_readable(fd: RawFd, timeout: Duration) -> io::Result<bool> {
+    let mut pollfd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    let timeout_ms = libc::c_int::try_from(timeout.as_millis()).unwrap_or(libc::c_int::MAX);
+
+    // SAFETY: `pollfd` is a valid `pollfd` for the duration of the call, and the
+    // count of `1` matches the single `pollfd` passed.
+    let n = unsafe { libc::poll(&mut pollfd, 1, timeout_ms) };
+    match n {
+        0 => Ok(false),
+        n if n > 0 => Ok(true),
+        _ => {
+            let error = io::Error::last_os_error();
+            if error.kind() == io::ErrorKind::Interrupted {
+                Ok(false)
+            } else {
+                Err(error)
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use serde::Serialize;
+
+use crate::{CmdCtx, Diagnostic, ErrorReport, FrameworkError, Log, Output, TypeParamsConstrained};
+
+/// Format that values are presented in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable text.
+    ///
+    /// Scalar values are written as is, and other values are written as YAML.
+    #[default]
+    Text,
+    /// One JSON document per line.
+    Json,
+    /// YAML document, started with `---`.
+    Yaml,
+}
+
+impl FromStr for OutputFormat {
+    type Err = ParseOutputFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "yaml" => Ok(Self::Yaml),
+            _ => Err(ParseOutputFormatError(s.to_string())),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text => "text".fmt(f),
+            Self::Json => "json".fmt(f),
+            Self::Yaml => "yaml".fmt(f),
+        }
+    }
+}
+
+/// Error parsing an `OutputFormat`.
+#[derive(Debug)]
+pub struct ParseOutputFormatError(pub String);
+
+impl std::error::Error for ParseOutputFormatError {}
+
+impl Display for ParseOutputFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid output format, expected one of: `text`, `json`, `yaml`",
+            self.0
+        )
+    }
+}
+
+/// Presentation layer on top of `Output`.
+pub trait OutputExt: Output {
+    /// Writes `t` in the given format.
+    fn present<T>(&mut self, output_format: OutputFormat, t: &T) -> Result<(), FrameworkError>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.write(&serialize(output_format, t)?)
+    }
+
+    /// Writes `error` and its causes in the given format, through
+    /// `Output::write_error`.
+    fn present_error<E>(
+        &mut self,
+        output_format: OutputFormat,
+        error: &E,
+    ) -> Result<(), FrameworkError>
+    where
+        E: Diagnostic + ?Sized,
+    {
+        let error_report = ErrorReport::new(error);
+        let serialized = match output_format {
+            OutputFormat::Text => error_report.to_string(),
+            OutputFormat::Json | OutputFormat::Yaml => serialize(output_format, &error_report)?,
+        };
+
+        self.write_error(&serialized)
+    }
+}
+
+impl<O> OutputExt for O where O: Output + ?Sized {}
+
+/// Value that can be presented through `Output`.
+///
+/// This is implemented for every `Serialize` type, and is object safe, so that
+/// values of different types can be presented as `Box<dyn Presentable>`.
+pub trait Presentable {
+    /// Writes this value to `output` in the given format.
+    fn present_to(
+        &self,
+        output: &mut dyn Output,
+        output_format: OutputFormat,
+    ) -> Result<(), FrameworkError>;
+}
+
+impl<T> Presentable for T
+where
+    T: Serialize + ?Sized,
+{
+    fn present_to(
+        &self,
+        output: &mut dyn Output,
+        output_format: OutputFormat,
+    ) -> Result<(), FrameworkError> {
+        output.present(output_format, self)
+    }
+}
+
+impl Presentable for Box<dyn Presentable> {
+    fn present_to(
+        &self,
+        output: &mut dyn Output,
+        output_format: OutputFormat,
+    ) -> Result<(), FrameworkError> {
+        self.as_ref().present_to(output, output_format)
+    }
+}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Writes `t` in the context's output format.
+    pub fn present<T>(&mut self, t: &T) -> Result<(), FrameworkError>
+    where
+        T: Presentable + ?Sized,
+    {
+        let CmdCtx {
+            output,
+            logger,
+            output_format,
+            ..
+        } = self;
+
+        logger.in_span("write", |_| t.present_to(output, *output_format))
+    }
+
+    /// Writes `error` and its causes in the context's output format.
+    pub fn present_error<E>(&mut self, error: &E) -> Result<(), FrameworkError>
+    where
+        E: Diagnostic + ?Sized,
+    {
+        let CmdCtx {
+            output,
+            logger,
+            output_format,
+            ..
+        } = self;
+
+        logger.in_span("write", |_| output.present_error(*output_format, error))
+    }
+}
+
+fn serialize<T>(output_format: OutputFormat, t: &T) -> Result<String, FrameworkError>
+where
+    T: Serialize + ?Sized,
+{
+    match output_format {
+        OutputFormat::Text => serialize_text(t),
+        OutputFormat::Json => serialize_json(t),
+        OutputFormat::Yaml => serialize_yaml_document(t),
+    }
+}
+
+fn serialize_text<T>(t: &T) -> Result<String, FrameworkError>
+where
+    T: Serialize + ?Sized,
+{
+    let value =
+        serde_json::to_value(t).map_err(|error| FrameworkError::Present(Box::new(error)))?;
+    let mut text = match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::Bool(b) => b.to_string(),
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s,
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => serialize_yaml(&value)?,
+    };
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+
+    Ok(text)
+}
+
+fn serialize_json<T>(t: &T) -> Result<String, FrameworkError>
+where
+    T: Serialize + ?Sized,
+{
+    let mut json =
+        serde_json::to_string(t).map_err(|error| FrameworkError::Present(Box::new(error)))?;
+    json.push('\n');
+
+    Ok(json)
+}
+
+/// Serializes `t` as a YAML document that starts with `---`, so that documents
+/// written one after another, such as progress events and the presented value,
+/// form a valid YAML stream.
+pub(crate) fn serialize_yaml_document<T>(t: &T) -> Result<String, FrameworkError>
+where
+    T: Serialize + ?Sized,
+{
+    serialize_yaml(t).map(|yaml| format!("---\n{yaml}"))
+}
+
+fn serialize_yaml<T>(t: &T) -> Result<String, FrameworkError>
+where
+    T: Serialize + ?Sized,
+{
+    serde_yaml::to_string(t).map_err(|error| FrameworkError::Present(Box::new(error)))
+}
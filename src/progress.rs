@@ -0,0 +1,258 @@
+use serde::Serialize;
+
+use crate::{
+    CmdCtx, FrameworkError, Output, OutputFormat, TypeParamsConstrained,
+    present::serialize_yaml_document,
+};
+
+/// Width of the bar drawn by `ProgressStyle::Bar`, in characters.
+const BAR_WIDTH: usize = 30;
+
+/// How progress is rendered to the output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProgressStyle {
+    /// A bar that is redrawn in place, for terminals.
+    Bar,
+    /// One line per update, for when the output is piped.
+    Lines,
+    /// One JSON event per line, for the `json` output format.
+    Json,
+    /// One YAML document per event, for the `yaml` output format.
+    Yaml,
+}
+
+impl ProgressStyle {
+    /// Returns the style for the given output and output format.
+    ///
+    /// Machine readable formats are rendered as events in the same format, so
+    /// they can be read from the same stream as the presented value. Text is
+    /// rendered as a bar on terminals, or lines otherwise.
+    pub fn for_output(output: &dyn Output, output_format: OutputFormat) -> Self {
+        match output_format {
+            OutputFormat::Text if output.is_terminal() => Self::Bar,
+            OutputFormat::Text => Self::Lines,
+            OutputFormat::Json => Self::Json,
+            OutputFormat::Yaml => Self::Yaml,
+        }
+    }
+}
+
+/// Handle that logic reports progress through.
+///
+/// Each update is rendered to the output straight away. The progress is
+/// finished when `finish` is called or the handle is dropped.
+pub struct Progress<'ctx> {
+    /// Output that progress is rendered to.
+    output: &'ctx mut dyn Output,
+    /// How progress is rendered.
+    style: ProgressStyle,
+    /// Number of steps, if known.
+    total: Option<u64>,
+    /// Number of steps completed.
+    position: u64,
+    /// Describes the current step.
+    message: String,
+    /// Whether `finish` has completed.
+    finished: bool,
+}
+
+impl<'ctx> Progress<'ctx> {
+    /// Returns a new `Progress` that renders to `output`.
+    ///
+    /// `total` is the number of steps, if known.
+    pub fn new(output: &'ctx mut dyn Output, style: ProgressStyle, total: Option<u64>) -> Self {
+        Self {
+            output,
+            style,
+            total,
+            position: 0,
+            message: String::new(),
+            finished: false,
+        }
+    }
+
+    /// Returns the number of steps, if known.
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// Returns the number of steps completed.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the percentage of steps completed, if the total is known.
+    pub fn percent(&self) -> Option<f64> {
+        self.total.map(|total| {
+            if total == 0 {
+                100.0
+            } else {
+                self.position.min(total) as f64 * 100.0 / total as f64
+            }
+        })
+    }
+
+    /// Returns the message describing the current step.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Sets the number of steps.
+    pub fn set_total(&mut self, total: Option<u64>) -> Result<(), FrameworkError> {
+        self.total = total;
+        self.render()
+    }
+
+    /// Marks `n` more steps as completed.
+    pub fn inc(&mut self, n: u64) -> Result<(), FrameworkError> {
+        self.position = self.position.saturating_add(n);
+        self.render()
+    }
+
+    /// Sets the number of steps completed.
+    pub fn set_position(&mut self, position: u64) -> Result<(), FrameworkError> {
+        self.position = position;
+        self.render()
+    }
+
+    /// Sets the message describing the current step.
+    pub fn set_message(&mut self, message: impl Into<String>) -> Result<(), FrameworkError> {
+        self.message = message.into();
+        self.render()
+    }
+
+    /// Marks `n` more steps as completed, and sets the message describing the
+    /// current step.
+    pub fn step(&mut self, n: u64, message: impl Into<String>) -> Result<(), FrameworkError> {
+        self.position = self.position.saturating_add(n);
+        self.message = message.into();
+        self.render()
+    }
+
+    /// Finishes the progress, ending the bar's line or writing the `finish`
+    /// event.
+    pub fn finish(mut self) -> Result<(), FrameworkError> {
+        self.finish_render()
+    }
+
+    fn render(&mut self) -> Result<(), FrameworkError> {
+        match self.style {
+            ProgressStyle::Bar => {
+                let line = self.line();
+                self.output.write(&format!("\r{line}\x1b[K"))?;
+                self.output.flush()
+            }
+            ProgressStyle::Lines => {
+                let line = self.line();
+                self.output.write(&format!("{line}\n"))
+            }
+            ProgressStyle::Json | ProgressStyle::Yaml => {
+                self.write_event(ProgressEventKind::Progress)
+            }
+        }
+    }
+
+    fn finish_render(&mut self) -> Result<(), FrameworkError> {
+        if self.finished {
+            return Ok(());
+        }
+        self.finished = true;
+
+        match self.style {
+            ProgressStyle::Bar => {
+                self.output.write("\n")?;
+                self.output.flush()
+            }
+            ProgressStyle::Lines => Ok(()),
+            ProgressStyle::Json | ProgressStyle::Yaml => {
+                self.write_event(ProgressEventKind::Finish)
+            }
+        }
+    }
+
+    /// Returns the progress as text, drawing a bar if the style is `Bar`.
+    fn line(&self) -> String {
+        let bar = match (self.style, self.percent()) {
+            (ProgressStyle::Bar, Some(percent)) => {
+                let filled = (percent / 100.0 * BAR_WIDTH as f64).round() as usize;
+                format!(
+                    "[{}{}] ",
+                    "#".repeat(filled),
+                    "-".repeat(BAR_WIDTH - filled)
+                )
+            }
+            _ => String::new(),
+        };
+        let count = match (self.total, self.percent()) {
+            (Some(total), Some(percent)) => format!("{}/{total} {percent:3.0}%", self.position),
+            _ => self.position.to_string(),
+        };
+
+        if self.message.is_empty() {
+            format!("{bar}{count}")
+        } else {
+            format!("{bar}{count} {}", self.message)
+        }
+    }
+
+    fn write_event(&mut self, kind: ProgressEventKind) -> Result<(), FrameworkError> {
+        let progress_event = ProgressEvent {
+            event: kind,
+            position: self.position,
+            total: self.total,
+            percent: self.percent(),
+            message: &self.message,
+        };
+        let event = match self.style {
+            ProgressStyle::Yaml => serialize_yaml_document(&progress_event)?,
+            ProgressStyle::Bar | ProgressStyle::Lines | ProgressStyle::Json => {
+                let mut json = serde_json::to_string(&progress_event)
+                    .map_err(|error| FrameworkError::Present(Box::new(error)))?;
+                json.push('\n');
+                json
+            }
+        };
+
+        self.output.write(&event)
+    }
+}
+
+impl Drop for Progress<'_> {
+    fn drop(&mut self) {
+        // Nothing can be done if the output cannot be written to.
+        let _ = self.finish_render();
+    }
+}
+
+/// Kind of event written by `ProgressStyle::Json` and `ProgressStyle::Yaml`.
+#[derive(Clone, Copy, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ProgressEventKind {
+    Progress,
+    Finish,
+}
+
+/// Event written by `ProgressStyle::Json` and `ProgressStyle::Yaml`.
+#[derive(Debug, Serialize)]
+struct ProgressEvent<'p> {
+    event: ProgressEventKind,
+    position: u64,
+    total: Option<u64>,
+    percent: Option<f64>,
+    message: &'p str,
+}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Returns a handle to report progress through, rendered to the output in
+    /// the style that suits it.
+    ///
+    /// `total` is the number of steps, if known.
+    pub fn progress(&mut self, total: Option<u64>) -> Progress<'_> {
+        let style = ProgressStyle::for_output(&self.output, self.output_format);
+
+        Progress::new(&mut self.output, style, total)
+    }
+}
@@ -0,0 +1,156 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use crate::{CmdCtx, FrameworkError, Output, TypeParamsConstrained};
+
+/// Checks text entered for `CmdCtx::text`, returning the message to show when
+/// the text is invalid.
+pub type Validator<'v> = &'v dyn Fn(&str) -> Result<(), String>;
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Asks a yes or no question, returning `default` if the line is empty.
+    ///
+    /// `y`, `yes`, `n`, and `no` are accepted in any case, and anything else is
+    /// asked again.
+    pub fn confirm(&mut self, question: &str, default: bool) -> Result<bool, FrameworkError> {
+        let choices = if default { "[Y/n]" } else { "[y/N]" };
+        loop {
+            self.output.write(&format!("{question} {choices} "))?;
+            self.output.flush()?;
+
+            let line = self.read_line()?;
+            match line.trim().to_lowercase().as_str() {
+                "" => return Ok(default),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => self.output.write("Please enter `y` or `n`.\n")?,
+            }
+        }
+    }
+
+    /// Asks for one of `options`, returning the index of the chosen option.
+    ///
+    /// The options are listed with numbers starting at 1, and anything other
+    /// than one of those numbers is asked again.
+    ///
+    /// Returns `FrameworkError::Usage` if there are no options.
+    pub fn select<T>(&mut self, question: &str, options: &[T]) -> Result<usize, FrameworkError>
+    where
+        T: Display,
+    {
+        if options.is_empty() {
+            return Err(FrameworkError::Usage(format!(
+                "no options to select from for `{question}`"
+            )));
+        }
+
+        self.output.write(&option_list(question, options))?;
+        loop {
+            self.output
+                .write(&format!("Enter a number (1-{}): ", options.len()))?;
+            self.output.flush()?;
+
+            let line = self.read_line()?;
+            match parse_option(line.trim(), options.len()) {
+                Ok(index) => return Ok(index),
+                Err(message) => self.output.write(&format!("{message}\n"))?,
+            }
+        }
+    }
+
+    /// Asks for any number of `options`, returning the indices of the chosen
+    /// options in ascending order.
+    ///
+    /// The options are listed with numbers starting at 1. Numbers are entered
+    /// separated by commas or spaces, and an empty line chooses none. If any
+    /// number is not one of the options, the question is asked again.
+    ///
+    /// Returns `FrameworkError::Usage` if there are no options.
+    pub fn multi_select<T>(
+        &mut self,
+        question: &str,
+        options: &[T],
+    ) -> Result<Vec<usize>, FrameworkError>
+    where
+        T: Display,
+    {
+        if options.is_empty() {
+            return Err(FrameworkError::Usage(format!(
+                "no options to select from for `{question}`"
+            )));
+        }
+
+        self.output.write(&option_list(question, options))?;
+        loop {
+            self.output.write(&format!(
+                "Enter numbers (1-{}) separated by commas, or nothing for none: ",
+                options.len()
+            ))?;
+            self.output.flush()?;
+
+            let line = self.read_line()?;
+            let indices = line
+                .split([',', ' ', '\t'])
+                .map(str::trim)
+                .filter(|number| !number.is_empty())
+                .map(|number| parse_option(number, options.len()))
+                .collect::<Result<BTreeSet<usize>, String>>();
+            match indices {
+                Ok(indices) => return Ok(indices.into_iter().collect()),
+                Err(message) => self.output.write(&format!("{message}\n"))?,
+            }
+        }
+    }
+
+    /// Asks for a line of text, asking again until every validator accepts
+    /// it.
+    ///
+    /// The text is returned without its trailing newline. When a validator
+    /// rejects the text, its message is shown before asking again.
+    pub fn text(
+        &mut self,
+        question: &str,
+        validators: &[Validator<'_>],
+    ) -> Result<String, FrameworkError> {
+        loop {
+            self.output.write(&format!("{question}: "))?;
+            self.output.flush()?;
+
+            let mut line = self.read_line()?;
+            let len = line.trim_end_matches(['\r', '\n']).len();
+            line.truncate(len);
+
+            match validators.iter().try_for_each(|validator| validator(&line)) {
+                Ok(()) => return Ok(line),
+                Err(message) => self.output.write(&format!("{message}\n"))?,
+            }
+        }
+    }
+}
+
+/// Returns `question` followed by each option on its own line, numbered from
+/// 1.
+fn option_list<T>(question: &str, options: &[T]) -> String
+where
+    T: Display,
+{
+    let number_width = options.len().to_string().len();
+    let mut option_list = format!("{question}\n");
+    options.iter().enumerate().for_each(|(index, option)| {
+        option_list.push_str(&format!("  {:>number_width$}) {option}\n", index + 1));
+    });
+
+    option_list
+}
+
+/// Parses an option's number, returning its index.
+fn parse_option(number: &str, option_count: usize) -> Result<usize, String> {
+    match number.parse::<usize>() {
+        Ok(number @ 1..) if number <= option_count => Ok(number - 1),
+        Ok(_) | Err(_) => Err(format!(
+            "`{number}` is not an option, enter a number from 1 to {option_count}."
+        )),
+    }
+}
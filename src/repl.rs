@@ -0,0 +1,104 @@
+use crate::{CmdCtx, Commands, Diagnostic, FrameworkError, Output, TypeParamsConstrained};
+
+/// Interactive loop that dispatches each line to a command.
+///
+/// Each line is split into a command name and its arguments. The command's
+/// return value is presented through `Output`, and errors are presented without
+/// ending the loop.
+///
+/// The loop ends at the end of input, or when `quit` is entered. `help` lists
+/// the commands, and `help <command>` shows the help text for a command.
+pub struct Repl<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Written before each line is read.
+    prompt: String,
+    /// Commands that can be dispatched to.
+    commands: Commands<Types>,
+}
+
+impl<Types> Repl<Types>
+where
+    Types: TypeParamsConstrained,
+    <Types as TypeParamsConstrained>::AppError: Diagnostic + From<FrameworkError>,
+{
+    /// Command that ends the loop.
+    pub const QUIT: &'static str = "quit";
+    /// Command that shows help text.
+    pub const HELP: &'static str = "help";
+
+    /// Returns a new `Repl` for the given commands, with `"> "` as the prompt.
+    pub fn new(commands: Commands<Types>) -> Self {
+        Self {
+            prompt: String::from("> "),
+            commands,
+        }
+    }
+
+    /// Sets the text written before each line is read.
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Runs the loop until the end of input, or `quit` is entered.
+    ///
+    /// Each line is read with `CmdCtx::read_line`, so the read timeout applies.
+    /// Returns `FrameworkError::Interrupted` if the context's cancel token is
+    /// cancelled.
+    pub fn run(
+        &mut self,
+        cmd_ctx: &mut CmdCtx<Types>,
+    ) -> Result<(), <Types as TypeParamsConstrained>::AppError> {
+        loop {
+            cmd_ctx.output.write(&self.prompt)?;
+            cmd_ctx.output.flush()?;
+
+            let line = match cmd_ctx.read_line() {
+                Ok(line) => line,
+                Err(FrameworkError::EndOfInput) => return Ok(()),
+                Err(error) => return Err(error.into()),
+            };
+            let line = line.trim();
+            let (name, args) = line
+                .split_once(char::is_whitespace)
+                .map(|(name, args)| (name, args.trim()))
+                .unwrap_or((line, ""));
+
+            match name {
+                "" => {}
+                Self::QUIT => return Ok(()),
+                Self::HELP => self.help(cmd_ctx, args)?,
+                _ => match self.commands.run(cmd_ctx, name, args) {
+                    Some(Ok(t)) => cmd_ctx.present(&t)?,
+                    Some(Err(error)) => cmd_ctx.present_error(&error)?,
+                    None => cmd_ctx.output.write(&format!(
+                        "Unknown command `{name}`. Enter `{}` to list commands.\n",
+                        Self::HELP
+                    ))?,
+                },
+            }
+        }
+    }
+
+    /// Writes the help text for `name`, or lists all commands if `name` is
+    /// empty.
+    fn help(&self, cmd_ctx: &mut CmdCtx<Types>, name: &str) -> Result<(), FrameworkError> {
+        if name.is_empty() {
+            let command_list = self.commands.help_list([
+                (Self::HELP, "Lists commands, or shows help for a command."),
+                (Self::QUIT, "Exits."),
+            ]);
+
+            cmd_ctx.output.write(&format!("Commands:\n{command_list}"))
+        } else {
+            match self.commands.help(name) {
+                Some(help) => cmd_ctx.output.write(&format!("{name}: {help}\n")),
+                None => cmd_ctx
+                    .output
+                    .write(&format!("Unknown command `{name}`.\n")),
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::{self, Display};
+
+use serde::Serialize;
+
+use crate::{FrameworkError, LogicError};
+
+/// Additional information to present with an error.
+///
+/// Implement this for the `AppError` to present it through `Output`; the
+/// default implementation has no help text.
+pub trait Diagnostic: std::error::Error {
+    /// Returns text that helps the user to resolve the error.
+    fn help(&self) -> Option<String> {
+        None
+    }
+}
+
+impl Diagnostic for LogicError {}
+
+impl Diagnostic for FrameworkError {
+    fn help(&self) -> Option<String> {
+        match self {
+            FrameworkError::Logic(_) => None,
+            FrameworkError::Input(_) => Some(String::from("check that the input is readable")),
+            FrameworkError::Output(_) => Some(String::from("check that the output is writable")),
+            FrameworkError::EndOfInput => {
+                Some(String::from("the input was closed before a line was read"))
+            }
+            FrameworkError::Usage(_) => None,
+            FrameworkError::Present(_) => None,
+            FrameworkError::Parse { input: _, error: _ } => Some(String::from(
+                "check that the input is in the expected format",
+            )),
+            FrameworkError::Resource(_) => Some(String::from(
+                "check that the resource is inserted, and that it is not borrowed elsewhere",
+            )),
+            FrameworkError::Interrupted => None,
+            FrameworkError::Timeout(phase) => Some(format!(
+                "the {phase} phase took too long, increase its timeout if it needs more time"
+            )),
+            FrameworkError::Retry { history, error: _ } => Some(history.clone()),
+        }
+    }
+}
+
+/// Report of an error, its chain of causes, and help text.
+///
+/// The `Display` implementation renders the report as text:
+///
+/// ```text
+/// error: Logic error
+///  --> caused by: failed to connect
+///  --> caused by: connection refused
+///   = help: check that the server is running
+/// ```
+///
+/// The `Serialize` implementation renders the report with `error`, `causes`,
+/// and `help` fields.
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorReport<'e, E>
+where
+    E: ?Sized,
+{
+    error: &'e E,
+}
+
+impl<'e, E> ErrorReport<'e, E>
+where
+    E: Diagnostic + ?Sized,
+{
+    /// Returns a new `ErrorReport` for the given error.
+    pub fn new(error: &'e E) -> Self {
+        Self { error }
+    }
+
+    /// Returns the top level error message.
+    pub fn message(&self) -> String {
+        self.error.to_string()
+    }
+
+    /// Returns the messages of each error in the `source()` chain.
+    pub fn causes(&self) -> Vec<String> {
+        let mut causes = Vec::new();
+        let mut source = self.error.source();
+        while let Some(error) = source {
+            causes.push(error.to_string());
+            source = error.source();
+        }
+
+        causes
+    }
+
+    /// Returns the help text of the error.
+    pub fn help(&self) -> Option<String> {
+        self.error.help()
+    }
+}
+
+impl<E> Display for ErrorReport<'_, E>
+where
+    E: Diagnostic + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "error: {}", self.message())?;
+        self.causes()
+            .iter()
+            .try_for_each(|cause| writeln!(f, " --> caused by: {cause}"))?;
+        if let Some(help) = self.help() {
+            // Continuation lines are aligned with the first line of help.
+            let help = help.replace('\n', "\n          ");
+            writeln!(f, "  = help: {help}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<E> Serialize for ErrorReport<'_, E>
+where
+    E: Diagnostic + ?Sized,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        #[derive(Serialize)]
+        struct ErrorReportSerialized {
+            error: String,
+            causes: Vec<String>,
+            help: Option<String>,
+        }
+
+        ErrorReportSerialized {
+            error: self.message(),
+            causes: self.causes(),
+            help: self.help(),
+        }
+        .serialize(serializer)
+    }
+}
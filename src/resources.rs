@@ -0,0 +1,258 @@
+use std::{
+    any::{Any, TypeId},
+    cell::{Ref, RefCell, RefMut},
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+use crate::{CmdCtx, CmdCtxBuilder, FrameworkError, TypeParamsT};
+
+/// Resources of any type, keyed by their type.
+///
+/// Unlike `State`, resources don't need to be declared in the `TypeParamsT`
+/// marker, so plugins and logic can insert and look up their own resources.
+///
+/// Borrows are checked at runtime: a resource may be borrowed many times, or
+/// mutably borrowed once.
+#[derive(Debug, Default)]
+pub struct Resources {
+    /// Resources by `TypeId`.
+    resources: HashMap<TypeId, Resource>,
+}
+
+/// A resource, with its type name for error messages.
+#[derive(Debug)]
+struct Resource {
+    /// Name of the resource's type.
+    type_name: &'static str,
+    /// The resource.
+    value: RefCell<Box<dyn Any + Send>>,
+}
+
+impl Resources {
+    /// Returns a new `Resources` with no resources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts `resource`, returning the resource of the same type that was
+    /// previously inserted.
+    pub fn insert<R>(&mut self, resource: R) -> Option<R>
+    where
+        R: Any + Send,
+    {
+        self.resources
+            .insert(
+                TypeId::of::<R>(),
+                Resource {
+                    type_name: std::any::type_name::<R>(),
+                    value: RefCell::new(Box::new(resource)),
+                },
+            )
+            .map(|resource| *downcast(resource.value.into_inner()))
+    }
+
+    /// Removes and returns the resource of type `R`.
+    pub fn remove<R>(&mut self) -> Option<R>
+    where
+        R: Any + Send,
+    {
+        self.resources
+            .remove(&TypeId::of::<R>())
+            .map(|resource| *downcast(resource.value.into_inner()))
+    }
+
+    /// Returns whether a resource of type `R` is inserted.
+    pub fn contains<R>(&self) -> bool
+    where
+        R: Any + Send,
+    {
+        self.resources.contains_key(&TypeId::of::<R>())
+    }
+
+    /// Borrows the resource of type `R`.
+    ///
+    /// Returns `FrameworkError::Resource` if the resource is not inserted, or is
+    /// mutably borrowed.
+    pub fn borrow<R>(&self) -> Result<Ref<'_, R>, FrameworkError>
+    where
+        R: Any + Send,
+    {
+        let resource = self.get::<R>()?;
+        let value = resource
+            .value
+            .try_borrow()
+            .map_err(|_| ResourceError::BorrowedMut {
+                type_name: resource.type_name,
+            })?;
+
+        Ok(Ref::map(value, |value| downcast_ref(value.as_ref())))
+    }
+
+    /// Mutably borrows the resource of type `R`.
+    ///
+    /// Returns `FrameworkError::Resource` if the resource is not inserted, or is
+    /// already borrowed.
+    pub fn borrow_mut<R>(&self) -> Result<RefMut<'_, R>, FrameworkError>
+    where
+        R: Any + Send,
+    {
+        let resource = self.get::<R>()?;
+        let value = resource
+            .value
+            .try_borrow_mut()
+            .map_err(|_| ResourceError::Borrowed {
+                type_name: resource.type_name,
+            })?;
+
+        Ok(RefMut::map(value, |value| downcast_mut(value.as_mut())))
+    }
+
+    fn get<R>(&self) -> Result<&Resource, ResourceError>
+    where
+        R: Any + Send,
+    {
+        self.resources
+            .get(&TypeId::of::<R>())
+            .ok_or(ResourceError::Missing {
+                type_name: std::any::type_name::<R>(),
+            })
+    }
+}
+
+// Resources are keyed by their `TypeId`, so downcasting to the type they were
+// looked up with cannot fail.
+
+fn downcast<R>(value: Box<dyn Any + Send>) -> Box<R>
+where
+    R: Any + Send,
+{
+    value
+        .downcast::<R>()
+        .expect("resource to be stored under its own `TypeId`")
+}
+
+fn downcast_ref<R>(value: &(dyn Any + Send)) -> &R
+where
+    R: Any + Send,
+{
+    value
+        .downcast_ref::<R>()
+        .expect("resource to be stored under its own `TypeId`")
+}
+
+fn downcast_mut<R>(value: &mut (dyn Any + Send)) -> &mut R
+where
+    R: Any + Send,
+{
+    value
+        .downcast_mut::<R>()
+        .expect("resource to be stored under its own `TypeId`")
+}
+
+/// Error when borrowing a resource.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceError {
+    /// No resource of the type is inserted.
+    Missing {
+        /// Name of the resource's type.
+        type_name: &'static str,
+    },
+    /// The resource could not be mutably borrowed, as it is already borrowed.
+    Borrowed {
+        /// Name of the resource's type.
+        type_name: &'static str,
+    },
+    /// The resource could not be borrowed, as it is already mutably borrowed.
+    BorrowedMut {
+        /// Name of the resource's type.
+        type_name: &'static str,
+    },
+}
+
+impl std::error::Error for ResourceError {}
+
+impl Display for ResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceError::Missing { type_name } => {
+                write!(f, "resource `{type_name}` is not inserted")
+            }
+            ResourceError::Borrowed { type_name } => write!(
+                f,
+                "resource `{type_name}` cannot be mutably borrowed, as it is already borrowed"
+            ),
+            ResourceError::BorrowedMut { type_name } => write!(
+                f,
+                "resource `{type_name}` cannot be borrowed, as it is already mutably borrowed"
+            ),
+        }
+    }
+}
+
+impl From<ResourceError> for FrameworkError {
+    fn from(error: ResourceError) -> Self {
+        Self::Resource(error)
+    }
+}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsT,
+{
+    /// Returns the resources that logic can insert and look up by type.
+    pub fn resources(&self) -> &Resources {
+        &self.resources
+    }
+
+    /// Returns a mutable reference to the resources.
+    pub fn resources_mut(&mut self) -> &mut Resources {
+        &mut self.resources
+    }
+
+    /// Inserts `resource`, returning the resource of the same type that was
+    /// previously inserted.
+    pub fn insert<R>(&mut self, resource: R) -> Option<R>
+    where
+        R: Any + Send,
+    {
+        self.resources.insert(resource)
+    }
+
+    /// Borrows the resource of type `R`.
+    ///
+    /// Returns `FrameworkError::Resource` if the resource is not inserted, or is
+    /// mutably borrowed.
+    pub fn borrow<R>(&self) -> Result<Ref<'_, R>, FrameworkError>
+    where
+        R: Any + Send,
+    {
+        self.resources.borrow::<R>()
+    }
+
+    /// Mutably borrows the resource of type `R`.
+    ///
+    /// Returns `FrameworkError::Resource` if the resource is not inserted, or is
+    /// already borrowed.
+    pub fn borrow_mut<R>(&self) -> Result<RefMut<'_, R>, FrameworkError>
+    where
+        R: Any + Send,
+    {
+        self.resources.borrow_mut::<R>()
+    }
+}
+
+impl<Types> CmdCtxBuilder<Types>
+where
+    Types: TypeParamsT,
+{
+    /// Inserts a resource into the `CmdCtx`, replacing any resource of the
+    /// same type.
+    pub fn with_resource<R>(mut self, resource: R) -> Self
+    where
+        R: Any + Send,
+    {
+        self.resources.insert(resource);
+        self
+    }
+}
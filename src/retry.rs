@@ -0,0 +1,339 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt::{self, Display},
+    hash::{BuildHasher, Hasher},
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+use crate::{
+    CmdCtx, FrameworkError, Level, Log, Logic, Output, OutputFormat, TypeParamsConstrained,
+};
+
+/// How long a backoff sleeps between checks of the cancel token.
+const CANCEL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Runs `Logic` again when it fails, waiting longer between each attempt.
+///
+/// The delay starts at the initial backoff and doubles after each attempt, up
+/// to the maximum backoff. Each delay is jittered to between half and all of
+/// its value, so that many commands retrying at once don't stay in step.
+///
+/// Each retry is reported through the context's `Output`, as a line of text, or
+/// as a `retry` event in the `json` and `yaml` output formats so that the
+/// output stays machine readable. If every attempt fails, the returned `RetryError` holds the error from each attempt. An error
+/// that isn't retryable is returned as is, as retrying would not help.
+#[derive(Clone, Debug)]
+pub struct Retry<L, P> {
+    /// Logic to run.
+    logic: L,
+    /// Decides whether an error is retried.
+    retryable: P,
+    /// Maximum number of attempts, including the first.
+    attempts: u32,
+    /// Delay before the first retry.
+    backoff_initial: Duration,
+    /// Longest delay between attempts.
+    backoff_max: Duration,
+}
+
+impl<L> Retry<L, fn(&L::Error) -> bool>
+where
+    L: Logic,
+{
+    /// Returns a new `Retry` that makes up to 3 attempts, retrying every error,
+    /// with a backoff starting at 100 milliseconds.
+    pub fn new(logic: L) -> Self {
+        Self {
+            logic,
+            retryable: |_| true,
+            attempts: 3,
+            backoff_initial: Duration::from_millis(100),
+            backoff_max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl<L, P> Retry<L, P>
+where
+    L: Logic,
+    P: FnMut(&L::Error) -> bool,
+{
+    /// Sets the maximum number of attempts, including the first.
+    ///
+    /// At least one attempt is always made.
+    pub fn with_attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry, which doubles after each
+    /// attempt.
+    pub fn with_backoff(mut self, backoff_initial: Duration) -> Self {
+        self.backoff_initial = backoff_initial;
+        self
+    }
+
+    /// Sets the longest delay between attempts, which also caps the initial
+    /// backoff.
+    pub fn with_backoff_max(mut self, backoff_max: Duration) -> Self {
+        self.backoff_max = backoff_max;
+        self
+    }
+
+    /// Only retries errors that `retryable` returns `true` for.
+    pub fn retry_if<PNext>(self, retryable: PNext) -> Retry<L, PNext>
+    where
+        PNext: FnMut(&L::Error) -> bool,
+    {
+        let Retry {
+            logic,
+            retryable: _,
+            attempts,
+            backoff_initial,
+            backoff_max,
+        } = self;
+
+        Retry {
+            logic,
+            retryable,
+            attempts,
+            backoff_initial,
+            backoff_max,
+        }
+    }
+
+    /// Runs the logic until it succeeds, fails with an error that isn't
+    /// retryable, or runs out of attempts.
+    ///
+    /// An error that isn't retryable is returned as is, so it keeps its own
+    /// exit code, and `RetryError` is only returned when every attempt fails.
+    ///
+    /// Backoff stops early with `FrameworkError::Interrupted` or
+    /// `FrameworkError::Timeout` if the context's cancel token is cancelled.
+    pub fn run<Types>(
+        &mut self,
+        cmd_ctx: &mut CmdCtx<Types>,
+    ) -> Result<L::ReturnType, <Types as TypeParamsConstrained>::AppError>
+    where
+        Types: TypeParamsConstrained,
+        <Types as TypeParamsConstrained>::AppError:
+            From<L::Error> + From<RetryError<L::Error>> + From<FrameworkError>,
+    {
+        let mut attempts = Vec::new();
+        let mut backoff = self.backoff_initial.min(self.backoff_max);
+        loop {
+            let start = Instant::now();
+            let error = match self.logic.do_work() {
+                Ok(t) => return Ok(t),
+                Err(error) => error,
+            };
+            let elapsed = start.elapsed();
+            let number = attempts.len() as u32 + 1;
+
+            if !(self.retryable)(&error) {
+                cmd_ctx.logger.event(
+                    Level::Warn,
+                    &format!("attempt {number} failed, not retrying: {error}"),
+                );
+                return Err(error.into());
+            }
+            if number >= self.attempts {
+                attempts.push(Attempt {
+                    number,
+                    error,
+                    elapsed,
+                    delay: None,
+                });
+                return Err(RetryError { attempts }.into());
+            }
+
+            let delay = jitter(backoff, number);
+            match cmd_ctx.output_format {
+                OutputFormat::Text => cmd_ctx.output.write(&format!(
+                    "Attempt {number} of {} failed: {error}. Retrying in {}ms.\n",
+                    self.attempts,
+                    delay.as_millis()
+                ))?,
+                OutputFormat::Json | OutputFormat::Yaml => cmd_ctx.present(&RetryEvent {
+                    event: "retry",
+                    attempt: number,
+                    attempts: self.attempts,
+                    error: error.to_string(),
+                    delay_ms: delay.as_millis(),
+                })?,
+            }
+            cmd_ctx.logger.event(
+                Level::Warn,
+                &format!("attempt {number} failed, retrying: {error}"),
+            );
+            attempts.push(Attempt {
+                number,
+                error,
+                elapsed,
+                delay: Some(delay),
+            });
+
+            sleep_cancellable(cmd_ctx, delay)?;
+            backoff = backoff.saturating_mul(2).min(self.backoff_max);
+        }
+    }
+
+    /// Returns the logic that is retried.
+    pub fn logic(&self) -> &L {
+        &self.logic
+    }
+}
+
+/// Returns a random delay between half and all of `backoff`.
+fn jitter(backoff: Duration, number: u32) -> Duration {
+    // `RandomState` is seeded randomly, which is enough to spread retries out
+    // without depending on a random number generator.
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(number);
+    let fraction = hasher.finish() as f64 / u64::MAX as f64;
+
+    backoff.mul_f64(0.5 + fraction * 0.5)
+}
+
+/// Sleeps for `delay`, stopping early if the context's cancel token is
+/// cancelled.
+fn sleep_cancellable<Types>(cmd_ctx: &CmdCtx<Types>, delay: Duration) -> Result<(), FrameworkError>
+where
+    Types: TypeParamsConstrained,
+{
+    let deadline = Instant::now() + delay;
+    loop {
+        cmd_ctx.cancel_token().check()?;
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Ok(());
+        }
+        std::thread::sleep(remaining.min(CANCEL_CHECK_INTERVAL));
+    }
+}
+
+/// Event written for each retry in the `json` and `yaml` output formats.
+#[derive(Debug, Serialize)]
+struct RetryEvent {
+    event: &'static str,
+    attempt: u32,
+    attempts: u32,
+    error: String,
+    delay_ms: u128,
+}
+
+/// A failed attempt made by `Retry`.
+#[derive(Clone, Debug)]
+pub struct Attempt<E> {
+    /// Number of the attempt, starting at 1.
+    pub number: u32,
+    /// Error that the attempt failed with.
+    pub error: E,
+    /// How long the attempt took.
+    pub elapsed: Duration,
+    /// How long was waited before the next attempt, if there was one.
+    pub delay: Option<Duration>,
+}
+
+/// Error when every attempt made by `Retry` fails.
+#[derive(Clone, Debug)]
+pub struct RetryError<E> {
+    /// Each attempt, in order. This is never empty.
+    attempts: Vec<Attempt<E>>,
+}
+
+impl<E> RetryError<E> {
+    /// Returns each attempt, in order.
+    pub fn attempts(&self) -> &[Attempt<E>] {
+        &self.attempts
+    }
+
+    /// Returns the error from the last attempt.
+    pub fn last_error(&self) -> &E {
+        // `Retry` only returns a `RetryError` after an attempt fails.
+        &self
+            .attempts
+            .last()
+            .expect("`RetryError` to have at least one attempt")
+            .error
+    }
+
+    /// Returns the history of attempts, one line per attempt.
+    pub fn history(&self) -> String
+    where
+        E: Display,
+    {
+        self.attempts
+            .iter()
+            .map(|attempt| {
+                let Attempt {
+                    number,
+                    error,
+                    elapsed,
+                    delay,
+                } = attempt;
+                let elapsed = elapsed.as_millis();
+                match delay {
+                    Some(delay) => format!(
+                        "attempt {number} failed after {elapsed}ms: {error}, retried after {}ms",
+                        delay.as_millis()
+                    ),
+                    None => format!("attempt {number} failed after {elapsed}ms: {error}"),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Maps the error of each attempt with `f`.
+    pub fn map_err<F, ENext>(self, mut f: F) -> RetryError<ENext>
+    where
+        F: FnMut(E) -> ENext,
+    {
+        let attempts = self
+            .attempts
+            .into_iter()
+            .map(|attempt| Attempt {
+                number: attempt.number,
+                error: f(attempt.error),
+                elapsed: attempt.elapsed,
+                delay: attempt.delay,
+            })
+            .collect();
+
+        RetryError { attempts }
+    }
+}
+
+impl<E> std::error::Error for RetryError<E>
+where
+    E: std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.last_error())
+    }
+}
+
+impl<E> Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.attempts.len() {
+            1 => write!(f, "failed after 1 attempt"),
+            n => write!(f, "failed after {n} attempts"),
+        }
+    }
+}
+
+impl<E> From<RetryError<E>> for FrameworkError
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn from(error: RetryError<E>) -> Self {
+        Self::Retry {
+            history: error.history(),
+            error: Box::new(error),
+        }
+    }
+}
@@ -0,0 +1,411 @@
+use std::{
+    fmt,
+    io::{self, BufRead, Read, Stdin},
+};
+
+use zeroize::Zeroizing;
+
+use crate::{
+    ArgsInput, CancelToken, CmdCtx, FileInput, FrameworkError, Input, Log, MemoryInput, Output,
+    ScriptedInput, TimeoutPhase, TypeParamsConstrained,
+};
+
+/// Longest secret that `Stdin` reads, in bytes, including the newline.
+///
+/// The buffer is allocated with this capacity up front, as growing it would
+/// leave copies of the secret in memory that are not cleared.
+const SECRET_CAPACITY: usize = 4096;
+
+/// Text that is cleared from memory when it is dropped, e.g. a password or
+/// token.
+///
+/// The text is not shown by `Debug`, and must be accessed with `expose`.
+pub struct Secret {
+    /// The text, without its trailing newline.
+    value: Zeroizing<String>,
+}
+
+impl Secret {
+    /// Returns a new `Secret` holding `value`.
+    pub fn new(value: String) -> Self {
+        Self {
+            value: Zeroizing::new(value),
+        }
+    }
+
+    /// Returns a new `Secret` for a line that is read, without its trailing
+    /// newline.
+    fn from_line(mut line: String) -> Self {
+        let len = line.trim_end_matches(['\r', '\n']).len();
+        line.truncate(len);
+
+        Self::new(line)
+    }
+
+    /// Returns the text.
+    pub fn expose(&self) -> &str {
+        &self.value
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret(..)")
+    }
+}
+
+/// `Input` that can read a line without showing it, e.g. for passwords.
+///
+/// The default implementation reads the line as usual, which suits inputs that
+/// aren't typed into, such as scripted input.
+pub trait SecretInput: Input {
+    /// Reads the next line without echoing it, returning the error from
+    /// `cancel_token.check()` if the token is cancelled or its deadline passes.
+    fn read_secret(&mut self, cancel_token: &CancelToken) -> Result<Secret, FrameworkError> {
+        self.read_cancellable(cancel_token).map(Secret::from_line)
+    }
+}
+
+/// Turns off echo when stdin is a terminal, and falls back to reading with
+/// echo otherwise.
+///
+/// On a terminal on unix, the line is read from the file descriptor straight
+/// into a buffer that is cleared on drop, so that it doesn't pass through the
+/// buffer of `Stdin`. Otherwise, the line is read through the buffer of
+/// `Stdin`, which keeps a copy of it until the buffer is overwritten.
+impl SecretInput for Stdin {
+    fn read_secret(&mut self, cancel_token: &CancelToken) -> Result<Secret, FrameworkError> {
+        #[cfg(unix)]
+        if io::IsTerminal::is_terminal(self) {
+            let fd = std::os::fd::AsRawFd::as_raw_fd(self);
+            let _echo_off = echo::EchoOff::new(fd).map_err(FrameworkError::Input)?;
+            crate::poll::wait_readable(fd, cancel_token)?;
+
+            cancel_token.check()?;
+            let secret = read_secret_fd(fd, cancel_token)?;
+            cancel_token.check()?;
+
+            return Ok(secret);
+        }
+
+        cancel_token.check()?;
+        let secret = read_secret_line(&mut self.lock())?;
+        cancel_token.check()?;
+
+        Ok(secret)
+    }
+}
+
+/// Reads a line from `reader` into a buffer that is cleared on drop.
+///
+/// The buffer never grows past its initial capacity, so it is not copied when
+/// reading. `reader`'s own buffer may still hold the line. Lines longer than
+/// `SECRET_CAPACITY` are rejected.
+fn read_secret_line<R>(reader: &mut R) -> Result<Secret, FrameworkError>
+where
+    R: BufRead,
+{
+    let mut bytes = Zeroizing::new(Vec::with_capacity(SECRET_CAPACITY));
+    reader
+        .take(SECRET_CAPACITY as u64)
+        .read_until(b'\n', &mut bytes)
+        .map_err(FrameworkError::Input)?;
+
+    secret_from_bytes(bytes)
+}
+
+/// Reads a line from the terminal `fd` into a buffer that is cleared on drop,
+/// without any other buffer in between.
+///
+/// A terminal returns at most one line per `read`, so nothing after the line
+/// is consumed. Lines longer than `SECRET_CAPACITY` are rejected.
+#[cfg(unix)]
+fn read_secret_fd(
+    fd: std::os::fd::RawFd,
+    cancel_token: &CancelToken,
+) -> Result<Secret, FrameworkError> {
+    let mut bytes = Zeroizing::new(Vec::<u8>::with_capacity(SECRET_CAPACITY));
+    while !bytes.ends_with(b"\n") && bytes.len() < SECRET_CAPACITY {
+        let len = bytes.len();
+        let spare = bytes.spare_capacity_mut();
+        // SAFETY: `spare` is valid for writes of `spare.len()` bytes.
+        let n = unsafe { libc::read(fd, spare.as_mut_ptr().cast(), spare.len()) };
+        match usize::try_from(n) {
+            Ok(0) => break,
+            // SAFETY: `read` initialized the first `n` bytes of `spare`.
+            Ok(n) => unsafe { bytes.set_len(len + n) },
+            Err(_) => {
+                let error = io::Error::last_os_error();
+                if error.kind() != io::ErrorKind::Interrupted {
+                    return Err(FrameworkError::Input(error));
+                }
+                cancel_token.check()?;
+            }
+        }
+    }
+
+    secret_from_bytes(bytes)
+}
+
+/// Returns the `Secret` for a line that was read into `bytes`.
+///
+/// Returns `FrameworkError::EndOfInput` if `bytes` is empty, and an
+/// `io::ErrorKind::InvalidData` error if `bytes` filled `SECRET_CAPACITY`
+/// without a newline, or is not valid UTF-8.
+fn secret_from_bytes(mut bytes: Zeroizing<Vec<u8>>) -> Result<Secret, FrameworkError> {
+    if bytes.is_empty() {
+        return Err(FrameworkError::EndOfInput);
+    }
+    if bytes.len() == SECRET_CAPACITY && !bytes.ends_with(b"\n") {
+        return Err(FrameworkError::Input(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("secret is longer than {SECRET_CAPACITY} bytes"),
+        )));
+    }
+
+    // `from_utf8` takes the buffer without copying it, and the buffer is
+    // cleared by `Zeroizing` if it isn't valid UTF-8.
+    String::from_utf8(std::mem::take(&mut *bytes))
+        .map(Secret::from_line)
+        .map_err(|error| {
+            drop(Zeroizing::new(error.into_bytes()));
+            FrameworkError::Input(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "secret is not valid UTF-8",
+            ))
+        })
+}
+
+/// Restores the terminal if echo was turned off while reading a secret.
+///
+/// This is for when the process exits without dropping the guard that turns
+/// echo back on, e.g. on a second Ctrl-C, as `std::process::exit` doesn't run
+/// destructors.
+pub(crate) fn restore_terminal() {
+    #[cfg(unix)]
+    echo::restore();
+}
+
+impl SecretInput for MemoryInput {}
+
+impl SecretInput for ScriptedInput {}
+
+impl SecretInput for FileInput {}
+
+impl SecretInput for ArgsInput {}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsConstrained,
+    <Types as TypeParamsConstrained>::Input: SecretInput,
+{
+    /// Writes `prompt`, and reads a line without echoing it.
+    ///
+    /// The read is within the read timeout, but the default line is not used
+    /// for secrets.
+    pub fn prompt_secret(&mut self, prompt: &str) -> Result<Secret, FrameworkError> {
+        let CmdCtx {
+            input,
+            output,
+            logger,
+            cancel_token,
+            timeouts,
+            ..
+        } = self;
+
+        logger.in_span("read", |_| {
+            output.write(prompt)?;
+            output.flush()?;
+
+            cancel_token.with_timeout(TimeoutPhase::Read, timeouts.read(), || {
+                input.read_secret(cancel_token)
+            })
+        })
+    }
+}
+
+#[cfg(unix)]
+mod echo {
+    use std::{
+        io,
+        mem::MaybeUninit,
+        os::fd::RawFd,
+        sync::{Mutex, PoisonError},
+    };
+
+    /// Terminal and settings to restore if the process exits while echo is
+    /// off, as `Drop` is not run by `std::process::exit`.
+    static TERMIOS_SAVED: Mutex<Option<(RawFd, libc::termios)>> = Mutex::new(None);
+
+    /// Turns off echo on a terminal until dropped.
+    ///
+    /// The newline is still echoed, so that the cursor moves to the next line
+    /// when the secret is entered.
+    pub(super) struct EchoOff {
+        /// File descriptor of the terminal.
+        fd: RawFd,
+        /// Terminal settings to restore.
+        termios: libc::termios,
+    }
+
+    impl EchoOff {
+        pub(super) fn new(fd: RawFd) -> io::Result<Self> {
+            let mut termios = MaybeUninit::<libc::termios>::uninit();
+            // SAFETY: `tcgetattr` initializes `termios` when it returns `0`.
+            let termios = unsafe {
+                if libc::tcgetattr(fd, termios.as_mut_ptr()) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                termios.assume_init()
+            };
+
+            let mut termios_no_echo = termios;
+            termios_no_echo.c_lflag &= !libc::ECHO;
+            termios_no_echo.c_lflag |= libc::ECHONL;
+            // Saved before echo is turned off, so a signal that arrives in
+            // between still restores the terminal.
+            *TERMIOS_SAVED.lock().unwrap_or_else(PoisonError::into_inner) = Some((fd, termios));
+            // SAFETY: `termios_no_echo` is a valid `termios` from `tcgetattr`.
+            if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &termios_no_echo) } != 0 {
+                *TERMIOS_SAVED.lock().unwrap_or_else(PoisonError::into_inner) = None;
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Self { fd, termios })
+        }
+    }
+
+    impl Drop for EchoOff {
+        fn drop(&mut self) {
+            *TERMIOS_SAVED.lock().unwrap_or_else(PoisonError::into_inner) = None;
+            tcsetattr(self.fd, &self.termios);
+        }
+    }
+
+    /// Restores the saved terminal settings, if echo is off.
+    pub(super) fn restore() {
+        let termios_saved = TERMIOS_SAVED
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some((fd, termios)) = termios_saved {
+            tcsetattr(fd, &termios);
+        }
+    }
+
+    fn tcsetattr(fd: RawFd, termios: &libc::termios) {
+        // SAFETY: `termios` is a valid `termios` from `tcgetattr`.
+        //
+        // Nothing can be done if the settings cannot be restored.
+        let _ = unsafe { libc::tcsetattr(fd, libc::TCSANOW, termios) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    #[cfg(unix)]
+    use std::os::fd::RawFd;
+
+    use super::{SECRET_CAPACITY, read_secret_line};
+    use crate::{CancelToken, FrameworkError};
+
+    #[test]
+    fn read_secret_line_trims_newline() {
+        let secret = read_secret_line(&mut Cursor::new("hunter2\r\nnext\n")).unwrap();
+
+        assert_eq!("hunter2", secret.expose());
+    }
+
+    #[test]
+    fn read_secret_line_returns_end_of_input() {
+        let result = read_secret_line(&mut Cursor::new(""));
+
+        assert!(
+            matches!(result, Err(FrameworkError::EndOfInput)),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn read_secret_line_rejects_secret_longer_than_capacity() {
+        let line = "a".repeat(SECRET_CAPACITY + 1);
+
+        let result = read_secret_line(&mut Cursor::new(line));
+
+        assert!(
+            matches!(&result, Err(FrameworkError::Input(error)) if error.kind() == std::io::ErrorKind::InvalidData),
+            "{result:?}"
+        );
+    }
+
+    #[test]
+    fn read_secret_line_accepts_secret_that_fills_capacity() {
+        let line = format!("{}\n", "a".repeat(SECRET_CAPACITY - 1));
+
+        let secret = read_secret_line(&mut Cursor::new(line)).unwrap();
+
+        assert_eq!(SECRET_CAPACITY - 1, secret.expose().len());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_secret_fd_reads_line_from_fd() {
+        let (fd_read, fd_write) = pipe();
+        write_all(fd_write, b"hunter2\n");
+
+        let secret = super::read_secret_fd(fd_read, &CancelToken::new()).unwrap();
+
+        assert_eq!("hunter2", secret.expose());
+        close(fd_read);
+        close(fd_write);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_secret_fd_returns_end_of_input() {
+        let (fd_read, fd_write) = pipe();
+        close(fd_write);
+
+        let result = super::read_secret_fd(fd_read, &CancelToken::new());
+
+        assert!(
+            matches!(result, Err(FrameworkError::EndOfInput)),
+            "{result:?}"
+        );
+        close(fd_read);
+    }
+
+    #[cfg(unix)]
+    fn pipe() -> (RawFd, RawFd) {
+        let mut fds = [0; 2];
+        // SAFETY: `fds` is valid for writes of two file descriptors.
+        assert_eq!(0, unsafe { libc::pipe(fds.as_mut_ptr()) });
+
+        (fds[0], fds[1])
+    }
+
+    #[cfg(unix)]
+    fn write_all(fd: RawFd, bytes: &[u8]) {
+        // SAFETY: `bytes` is valid for reads of `bytes.len()` bytes.
+        let n = unsafe { libc::write(fd, bytes.as_ptr().cast(), bytes.len()) };
+        assert_eq!(Ok(bytes.len()), usize::try_from(n));
+    }
+
+    #[cfg(unix)]
+    fn close(fd: RawFd) {
+        // SAFETY: `fd` is open, and is not used after this.
+        unsafe { libc::close(fd) };
+    }
+
+    #[test]
+    fn read_secret_line_rejects_invalid_utf8() {
+        let result = read_secret_line(&mut Cursor::new(b"\xff\n".to_vec()));
+
+        assert!(
+            matches!(&result, Err(FrameworkError::Input(error)) if error.kind() == std::io::ErrorKind::InvalidData),
+            "{result:?}"
+        );
+    }
+}
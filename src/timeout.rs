@@ -0,0 +1,192 @@
+use std::{
+    fmt::{self, Display},
+    time::Duration,
+};
+
+use crate::{
+    CancelToken, CmdCtx, CmdCtxBuilder, FrameworkError, Input, Level, Log, Output,
+    TypeParamsConstrained, TypeParamsT,
+};
+
+/// Phase of a command that a timeout applies to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimeoutPhase {
+    /// Waiting for a line of input.
+    Read,
+    /// Running the logic.
+    Work,
+}
+
+impl Display for TimeoutPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimeoutPhase::Read => "read".fmt(f),
+            TimeoutPhase::Work => "work".fmt(f),
+        }
+    }
+}
+
+/// Timeouts for each phase of a command.
+///
+/// Timeouts are cooperative: reads and logic stop at their deadline with
+/// `FrameworkError::Timeout` when they check the context's `CancelToken`. Logic
+/// that doesn't check the token runs to completion, and its result is kept
+/// with a warning logged for the overrun.
+///
+/// Likewise, the read timeout only stops inputs that wait while checking the
+/// token, such as `ScriptedInput`, or `Stdin` when it is a terminal on unix.
+/// Piped `Stdin`, `Stdin` on other platforms, and inputs that don't override
+/// `Input::read_cancellable` block until a line arrives, and only return
+/// `FrameworkError::Timeout` afterwards if the deadline has passed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// How long to wait for a line of input.
+    read: Option<Duration>,
+    /// Line that is used when a read times out.
+    read_default: Option<String>,
+    /// How long the logic may run for.
+    work: Option<Duration>,
+}
+
+impl Timeouts {
+    /// Returns a new `Timeouts` without any timeouts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long to wait for a line of input.
+    ///
+    /// This is not enforced for every input, see the type's documentation.
+    pub fn with_read(mut self, read: Duration) -> Self {
+        self.read = Some(read);
+        self
+    }
+
+    /// Sets the line that is used when a read times out, instead of returning
+    /// `FrameworkError::Timeout`.
+    pub fn with_read_default(mut self, read_default: impl Into<String>) -> Self {
+        self.read_default = Some(read_default.into());
+        self
+    }
+
+    /// Sets how long the logic may run for.
+    pub fn with_work(mut self, work: Duration) -> Self {
+        self.work = Some(work);
+        self
+    }
+
+    /// Returns how long to wait for a line of input.
+    pub fn read(&self) -> Option<Duration> {
+        self.read
+    }
+
+    /// Returns the line that is used when a read times out.
+    pub fn read_default(&self) -> Option<&str> {
+        self.read_default.as_deref()
+    }
+
+    /// Returns how long the logic may run for.
+    pub fn work(&self) -> Option<Duration> {
+        self.work
+    }
+}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsT,
+{
+    /// Returns the timeouts for each phase of the command.
+    pub fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+}
+
+impl<Types> CmdCtx<Types>
+where
+    Types: TypeParamsConstrained,
+{
+    /// Reads a line within the read timeout.
+    ///
+    /// If the read times out, the default line is used if there is one,
+    /// otherwise `FrameworkError::Timeout` is returned.
+    pub fn read_line(&mut self) -> Result<String, FrameworkError> {
+        let CmdCtx {
+            input,
+            output,
+            logger,
+            cancel_token,
+            timeouts,
+            ..
+        } = self;
+
+        logger.in_span("read", |logger| {
+            read_line(input, output, logger, cancel_token, timeouts)
+        })
+    }
+}
+
+impl<Types> CmdCtxBuilder<Types>
+where
+    Types: TypeParamsT,
+{
+    /// Sets the timeouts for each phase of the command.
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+}
+
+/// Reads a line within the read timeout, falling back to the default line.
+///
+/// The default line is written to `output`, as it is not echoed like a line
+/// that is entered.
+pub(crate) fn read_line<I, O, L>(
+    input: &mut I,
+    output: &mut O,
+    logger: &mut L,
+    cancel_token: &CancelToken,
+    timeouts: &Timeouts,
+) -> Result<String, FrameworkError>
+where
+    I: Input + ?Sized,
+    O: Output + ?Sized,
+    L: Log + ?Sized,
+{
+    let result = cancel_token.with_timeout(TimeoutPhase::Read, timeouts.read(), || {
+        input.read_cancellable(cancel_token)
+    });
+
+    match (result, timeouts.read_default()) {
+        (Err(FrameworkError::Timeout(TimeoutPhase::Read)), Some(read_default)) => {
+            logger.event(Level::Warn, "read timed out, using the default");
+
+            let line = format!("{read_default}\n");
+            output.write(&line)?;
+            output.flush()?;
+
+            Ok(line)
+        }
+        (result, _) => result,
+    }
+}
+
+/// Logs a warning if the logic took longer than its work timeout.
+///
+/// Logic is only stopped at the deadline if it checks the cancel token, so the
+/// result of logic that overruns is kept, and the overrun is logged instead.
+pub(crate) fn warn_overrun<L>(logger: &mut L, work_timeout: Option<Duration>, elapsed: Duration)
+where
+    L: Log + ?Sized,
+{
+    match work_timeout {
+        Some(work_timeout) if elapsed > work_timeout => logger.event(
+            Level::Warn,
+            &format!(
+                "logic took {}ms, longer than its {}ms timeout",
+                elapsed.as_millis(),
+                work_timeout.as_millis()
+            ),
+        ),
+        Some(_) | None => {}
+    }
+}
@@ -0,0 +1,199 @@
+use assoc_type_params::{ArgMatches, ArgSpec, ArgsInput, FrameworkError, FromArgs, Input};
+
+#[test]
+fn parse_unspecified_infers_options_and_flags() {
+    let arg_matches =
+        ArgMatches::parse_unspecified(["--name", "a", "--verbose", "--count=2", "pos"]).unwrap();
+
+    assert_eq!(Some(String::from("a")), arg_matches.value("name").unwrap());
+    assert_eq!(2, arg_matches.required::<u32>("count").unwrap());
+    assert!(arg_matches.flag("verbose"));
+    assert_eq!(["pos"], arg_matches.positionals());
+    assert_eq!(["a", "2", "pos"], arg_matches.values());
+}
+
+#[test]
+fn parse_unspecified_treats_args_after_double_dash_as_positional() {
+    let arg_matches = ArgMatches::parse_unspecified(["--", "--name", "a"]).unwrap();
+
+    assert_eq!(["--name", "a"], arg_matches.positionals());
+    assert!(!arg_matches.flag("name"));
+}
+
+#[test]
+fn parse_unspecified_returns_usage_error_for_option_without_value() {
+    let result = ArgMatches::parse_unspecified(["--value", "foo", "--value"]);
+
+    assert!(
+        matches!(&result, Err(FrameworkError::Usage(usage)) if usage == "`--value` requires a value"),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn args_input_new_returns_usage_error_for_ambiguous_args() {
+    let result = ArgsInput::new(["--value", "foo", "--value"]);
+
+    assert!(
+        matches!(result, Err(FrameworkError::Usage(_))),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn args_input_reads_values_in_order() {
+    let mut args_input = ArgsInput::new(["--value", "foo", "bar"]).unwrap();
+
+    assert_eq!("foo\n", args_input.read().unwrap());
+    assert_eq!("bar\n", args_input.read().unwrap());
+    assert!(matches!(args_input.read(), Err(FrameworkError::EndOfInput)));
+}
+
+fn arg_spec() -> ArgSpec {
+    ArgSpec::new()
+        .option("name", "Name to greet.")
+        .flag("loud", "Greets loudly.")
+}
+
+#[test]
+fn arg_spec_parse_uses_declared_kinds() {
+    let arg_matches = arg_spec()
+        .parse(["--loud", "--name", "a", "--name=b"])
+        .unwrap();
+
+    assert!(arg_matches.flag("loud"));
+    assert_eq!(
+        vec![String::from("a"), String::from("b")],
+        arg_matches.values_of::<String>("name").unwrap()
+    );
+    assert_eq!(Some(String::from("b")), arg_matches.value("name").unwrap());
+}
+
+#[test]
+fn arg_spec_parse_returns_usage_error_for_unknown_argument() {
+    let result = arg_spec().parse(["--nope"]);
+
+    let expected = "unknown argument `--nope`\n\n\
+                    Arguments:\n\
+                    \x20 --name <value>  Name to greet.\n\
+                    \x20 --loud          Greets loudly.";
+    assert!(
+        matches!(&result, Err(FrameworkError::Usage(usage)) if usage == expected),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn arg_spec_parse_returns_usage_error_for_flag_with_value() {
+    let result = arg_spec().parse(["--loud=yes"]);
+
+    assert!(
+        matches!(&result, Err(FrameworkError::Usage(usage)) if usage == "`--loud` does not take a value"),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn required_returns_usage_error_when_missing() {
+    let arg_matches = arg_spec().parse(Vec::<String>::new()).unwrap();
+
+    let result = arg_matches.required::<String>("name");
+
+    assert!(
+        matches!(&result, Err(FrameworkError::Usage(usage)) if usage == "`--name` is required"),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn value_returns_parse_error_for_invalid_value() {
+    let arg_matches = arg_spec().parse(["--name", "x"]).unwrap();
+
+    let result = arg_matches.value::<u32>("name");
+
+    assert!(
+        matches!(&result, Err(FrameworkError::Parse { input, .. }) if input == "x"),
+        "{result:?}"
+    );
+}
+
+#[derive(Debug, PartialEq)]
+struct Greeting {
+    name: String,
+    loud: bool,
+}
+
+impl FromArgs for Greeting {
+    fn arg_spec() -> ArgSpec {
+        arg_spec()
+    }
+
+    fn from_arg_matches(arg_matches: &ArgMatches) -> Result<Self, FrameworkError> {
+        Ok(Self {
+            name: arg_matches.required("name")?,
+            loud: arg_matches.flag("loud"),
+        })
+    }
+}
+
+#[test]
+fn from_args_fills_in_type() {
+    let greeting = Greeting::from_args(["--name", "a", "--loud"]).unwrap();
+
+    assert_eq!(
+        Greeting {
+            name: String::from("a"),
+            loud: true,
+        },
+        greeting
+    );
+}
+
+#[test]
+fn parse_unspecified_returns_usage_error_for_name_with_and_without_value() {
+    let result = ArgMatches::parse_unspecified(["--v", "--v", "a"]);
+
+    assert!(
+        matches!(&result, Err(FrameworkError::Usage(usage)) if usage == "`--v` requires a value"),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn parse_unspecified_takes_single_dash_value() {
+    let arg_matches = ArgMatches::parse_unspecified(["--offset", "-5"]).unwrap();
+
+    assert_eq!(Some(-5), arg_matches.value::<i32>("offset").unwrap());
+    assert!(arg_matches.positionals().is_empty());
+}
+
+#[test]
+fn arg_spec_parse_does_not_take_double_dash_arg_as_value() {
+    let arg_spec = ArgSpec::new()
+        .option("value", "Value to use.")
+        .flag("verbose", "Shows more output.");
+
+    let result = arg_spec.parse(["--value", "--verbose"]);
+
+    assert!(
+        matches!(&result, Err(FrameworkError::Usage(usage)) if usage == "`--value` requires a value"),
+        "{result:?}"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn from_env_returns_usage_error_for_invalid_utf8() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt, process::Command};
+
+    let output = Command::new(env!("CARGO_BIN_EXE_assoc_type_params"))
+        .arg(OsStr::from_bytes(b"--value=\xff"))
+        .output()
+        .unwrap();
+
+    assert_eq!(Some(64), output.status.code());
+    assert_eq!(
+        "error: argument `--value=\u{FFFD}` is not valid UTF-8\n",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
@@ -0,0 +1,79 @@
+use assoc_type_params::{
+    AsyncAdapter, AsyncOutput, CmdCtx, CmdCtxBuilder, FrameworkError, MemoryInput, TypeParams,
+    run_async,
+};
+
+use crate::common::WorkLogic;
+
+mod common;
+
+/// Records writes, and how much was written when `flush` was last called.
+#[derive(Default)]
+struct FlushOutput {
+    written: String,
+    flushed: Option<usize>,
+}
+
+impl AsyncOutput for FlushOutput {
+    async fn write(&mut self, s: &str) -> Result<(), FrameworkError> {
+        self.written.push_str(s);
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<(), FrameworkError> {
+        self.flushed = Some(self.written.len());
+        Ok(())
+    }
+}
+
+type AsyncMemoryEndpoint = TypeParams<FrameworkError, AsyncAdapter<MemoryInput>, FlushOutput>;
+
+fn cmd_ctx<const N: usize>(lines: [&str; N]) -> CmdCtx<AsyncMemoryEndpoint> {
+    CmdCtxBuilder::new()
+        .with_input(AsyncAdapter(MemoryInput::new(lines)))
+        .with_output(FlushOutput::default())
+        .with_error::<FrameworkError>()
+        .build_async()
+}
+
+#[test]
+fn run_async_writes_transcript_and_flushes() {
+    let mut cmd_ctx = cmd_ctx(["hi"]);
+
+    let result = futures_executor::block_on(run_async(&mut cmd_ctx, &mut AsyncAdapter(WorkLogic)));
+
+    assert!(matches!(result, Ok(123)), "{result:?}");
+    assert_eq!(
+        "Enter some input:\nYou entered: hi\n",
+        cmd_ctx.output.written
+    );
+    assert_eq!(Some(cmd_ctx.output.written.len()), cmd_ctx.output.flushed);
+}
+
+#[test]
+fn run_async_flushes_on_error() {
+    let mut cmd_ctx = cmd_ctx([]);
+
+    let result = futures_executor::block_on(run_async(&mut cmd_ctx, &mut AsyncAdapter(WorkLogic)));
+
+    assert!(
+        matches!(result, Err(FrameworkError::EndOfInput)),
+        "{result:?}"
+    );
+    assert_eq!(Some("Enter some input:\n".len()), cmd_ctx.output.flushed);
+}
+
+#[test]
+fn run_async_stops_when_cancelled() {
+    let mut cmd_ctx = cmd_ctx(["hi"]);
+    cmd_ctx.cancel_token().cancel();
+
+    let result = futures_executor::block_on(run_async(&mut cmd_ctx, &mut AsyncAdapter(WorkLogic)));
+
+    assert!(
+        matches!(result, Err(FrameworkError::Interrupted)),
+        "{result:?}"
+    );
+    assert_eq!("Enter some input:\n", cmd_ctx.output.written);
+    assert_eq!(1, cmd_ctx.input.0.remaining().len());
+}
@@ -0,0 +1,105 @@
+use assoc_type_params::{
+    CmdCtx, Commands, CtxLogic, Dispatcher, FrameworkError, LogicError, MemoryEndpoint,
+};
+
+use crate::common::{Greet, cmd_ctx};
+
+mod common;
+
+struct Sum;
+impl CtxLogic<MemoryEndpoint> for Sum {
+    type Error = LogicError;
+    type Input = String;
+    type ReturnType = u32;
+
+    fn do_work(
+        &mut self,
+        _cmd_ctx: &mut CmdCtx<MemoryEndpoint>,
+        input: Self::Input,
+    ) -> Result<Self::ReturnType, Self::Error> {
+        input
+            .split_whitespace()
+            .map(|n| n.parse::<u32>().map_err(|e| LogicError(e.to_string())))
+            .sum()
+    }
+}
+
+fn dispatcher() -> Dispatcher<MemoryEndpoint> {
+    let commands = Commands::new()
+        .with_command("greet", "Says hello.", Greet)
+        .with_ctx_command("sum", "Adds numbers.", Sum);
+
+    Dispatcher::new("app", commands)
+}
+
+const USAGE: &str = "Usage: app <command> [args]\n\
+                     \n\
+                     Commands:\n\
+                     \x20 greet  Says hello.\n\
+                     \x20 sum    Adds numbers.\n\
+                     \x20 help   Lists commands, or shows help for a command.";
+
+#[test]
+fn dispatches_to_command_with_joined_args() {
+    let mut cmd_ctx = cmd_ctx([]);
+    let mut dispatcher = dispatcher();
+
+    let greeting = dispatcher.dispatch(&mut cmd_ctx, ["greet"]).unwrap();
+    let sum = dispatcher
+        .dispatch(&mut cmd_ctx, ["sum", "1", "2", "3"])
+        .unwrap();
+    cmd_ctx.present(&greeting).unwrap();
+    cmd_ctx.present(&sum).unwrap();
+
+    assert_eq!("hello\n6\n", cmd_ctx.output.transcript());
+}
+
+#[test]
+fn no_command_returns_usage_error() {
+    let result = dispatcher().dispatch(&mut cmd_ctx([]), Vec::<String>::new());
+
+    match result {
+        Err(FrameworkError::Usage(usage)) => assert_eq!(USAGE, usage),
+        Err(error) => panic!("expected usage error, got {error:?}"),
+        Ok(_) => panic!("expected usage error"),
+    }
+}
+
+#[test]
+fn unknown_command_returns_usage_error() {
+    let result = dispatcher().dispatch(&mut cmd_ctx([]), ["nope"]);
+
+    match result {
+        Err(FrameworkError::Usage(usage)) => {
+            assert_eq!(format!("unknown command `nope`\n\n{USAGE}"), usage)
+        }
+        Err(error) => panic!("expected usage error, got {error:?}"),
+        Ok(_) => panic!("expected usage error"),
+    }
+}
+
+#[test]
+fn help_shows_usage_or_command_help() {
+    let mut cmd_ctx = cmd_ctx([]);
+    let mut dispatcher = dispatcher();
+
+    let usage = dispatcher.dispatch(&mut cmd_ctx, ["help"]).unwrap();
+    let help = dispatcher.dispatch(&mut cmd_ctx, ["help", "sum"]).unwrap();
+    cmd_ctx.present(&usage).unwrap();
+    cmd_ctx.present(&help).unwrap();
+
+    assert_eq!(
+        format!("{USAGE}\nsum: Adds numbers.\n"),
+        cmd_ctx.output.transcript()
+    );
+}
+
+#[test]
+fn command_error_is_returned() {
+    let result = dispatcher().dispatch(&mut cmd_ctx([]), ["sum", "1", "x"]);
+
+    assert!(
+        matches!(result, Err(FrameworkError::Logic(_))),
+        "expected logic error"
+    );
+}
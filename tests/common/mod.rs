@@ -0,0 +1,46 @@
+//! Fixtures shared by the integration tests.
+
+// Each test file is its own crate, and uses only some of the fixtures.
+#![allow(dead_code)]
+
+use assoc_type_params::{
+    CmdCtx, CmdCtxBuilder, FrameworkError, Logic, LogicError, MemoryEndpoint, MemoryInput,
+    MemoryOutput, TypeParams,
+};
+
+/// Logic that returns `123`, like the logic in `main.rs`.
+pub struct WorkLogic;
+impl Logic for WorkLogic {
+    type Error = LogicError;
+    type ReturnType = u8;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        Ok(123)
+    }
+}
+
+/// Logic that returns `"hello"`.
+pub struct Greet;
+impl Logic for Greet {
+    type Error = LogicError;
+    type ReturnType = String;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        Ok(String::from("hello"))
+    }
+}
+
+/// Returns a `CmdCtxBuilder` that reads `lines` and records what is written.
+pub fn cmd_ctx_builder<const N: usize>(
+    lines: [&str; N],
+) -> CmdCtxBuilder<TypeParams<FrameworkError, MemoryInput, MemoryOutput>> {
+    CmdCtxBuilder::new()
+        .with_input(MemoryInput::new(lines))
+        .with_output(MemoryOutput::new())
+        .with_error::<FrameworkError>()
+}
+
+/// Returns a `CmdCtx` that reads `lines` and records what is written.
+pub fn cmd_ctx<const N: usize>(lines: [&str; N]) -> CmdCtx<MemoryEndpoint> {
+    cmd_ctx_builder(lines).build_as::<MemoryEndpoint>()
+}
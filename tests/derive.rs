@@ -0,0 +1,12 @@
+#[test]
+fn type_params_derive() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass_*.rs");
+    t.compile_fail("tests/ui/fail_*.rs");
+
+    // The error lists each `Log` implementation, which includes `TracingLog`
+    // when the feature is enabled.
+    if !cfg!(feature = "tracing") {
+        t.compile_fail("tests/ui/logger_fail_not_log.rs");
+    }
+}
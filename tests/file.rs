@@ -0,0 +1,165 @@
+use std::{fs, process::ExitCode};
+
+use assoc_type_params::{
+    CmdCtxBuilder, FileEndpoint, FileInput, FileOutput, FileOutputMode, FrameworkError, Logic,
+    LogicError, Output, run, run_main,
+};
+
+use crate::common::WorkLogic;
+
+mod common;
+
+struct FailLogic;
+impl Logic for FailLogic {
+    type Error = LogicError;
+    type ReturnType = u8;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        Err(LogicError(String::from("x")))
+    }
+}
+
+const ERROR_REPORT: &str = "error: Logic error\n --> caused by: x\n";
+
+#[test]
+fn run_main_failure_leaves_atomic_destination_untouched() {
+    let dir = tempfile::tempdir().unwrap();
+    let path_in = dir.path().join("in.txt");
+    let path_out = dir.path().join("out.txt");
+    fs::write(&path_in, "hi\n").unwrap();
+    let path_err = dir.path().join("err.txt");
+    fs::write(&path_out, "GOOD\n").unwrap();
+
+    let mut cmd_ctx = CmdCtxBuilder::new()
+        .with_input(FileInput::open(&path_in).unwrap())
+        .with_output(
+            FileOutput::open_atomic(&path_out, FileOutputMode::Truncate)
+                .unwrap()
+                .with_error_writer(fs::File::create(&path_err).unwrap()),
+        )
+        .with_error::<FrameworkError>()
+        .build_as::<FileEndpoint>();
+
+    let exit_code = run_main(&mut cmd_ctx, |cmd_ctx| run(cmd_ctx, &mut FailLogic));
+    drop(cmd_ctx);
+
+    assert_ne!(ExitCode::SUCCESS, exit_code);
+    assert_eq!("GOOD\n", fs::read_to_string(&path_out).unwrap());
+    assert_eq!(ERROR_REPORT, fs::read_to_string(&path_err).unwrap());
+    assert_eq!(
+        ["err.txt", "in.txt", "out.txt"],
+        file_names(dir.path()).as_slice(),
+        "temporary file should be removed"
+    );
+}
+
+#[test]
+fn run_main_failure_does_not_write_error_into_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path_in = dir.path().join("in.txt");
+    let path_out = dir.path().join("out.txt");
+    let path_err = dir.path().join("err.txt");
+    fs::write(&path_in, "hi\n").unwrap();
+
+    let mut cmd_ctx = CmdCtxBuilder::new()
+        .with_input(FileInput::open(&path_in).unwrap())
+        .with_output(
+            FileOutput::open(&path_out, FileOutputMode::Truncate)
+                .unwrap()
+                .with_error_writer(fs::File::create(&path_err).unwrap()),
+        )
+        .with_error::<FrameworkError>()
+        .build_as::<FileEndpoint>();
+
+    let exit_code = run_main(&mut cmd_ctx, |cmd_ctx| run(cmd_ctx, &mut FailLogic));
+    drop(cmd_ctx);
+
+    assert_ne!(ExitCode::SUCCESS, exit_code);
+    assert_eq!(
+        "Enter some input:\n",
+        fs::read_to_string(&path_out).unwrap()
+    );
+    assert_eq!(ERROR_REPORT, fs::read_to_string(&path_err).unwrap());
+}
+
+#[test]
+fn run_main_success_replaces_atomic_destination() {
+    let dir = tempfile::tempdir().unwrap();
+    let path_in = dir.path().join("in.txt");
+    let path_out = dir.path().join("out.txt");
+    fs::write(&path_in, "hi\n").unwrap();
+    fs::write(&path_out, "GOOD\n").unwrap();
+
+    let mut cmd_ctx = CmdCtxBuilder::new()
+        .with_input(FileInput::open(&path_in).unwrap())
+        .with_output(FileOutput::open_atomic(&path_out, FileOutputMode::Truncate).unwrap())
+        .with_error::<FrameworkError>()
+        .build_as::<FileEndpoint>();
+
+    let exit_code = run_main(&mut cmd_ctx, |cmd_ctx| run(cmd_ctx, &mut WorkLogic));
+    drop(cmd_ctx);
+
+    assert_eq!(ExitCode::SUCCESS, exit_code);
+    assert_eq!(
+        "Enter some input:\nYou entered: hi\n123\n",
+        fs::read_to_string(&path_out).unwrap()
+    );
+}
+
+#[test]
+fn open_atomic_create_fails_when_destination_exists() {
+    let dir = tempfile::tempdir().unwrap();
+    let path_out = dir.path().join("out.txt");
+    fs::write(&path_out, "GOOD\n").unwrap();
+
+    let result = FileOutput::open_atomic(&path_out, FileOutputMode::Create);
+
+    assert!(
+        matches!(&result, Err(FrameworkError::Output(error)) if error.kind() == std::io::ErrorKind::AlreadyExists),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn finish_atomic_create_does_not_replace_destination_created_after_open() {
+    let dir = tempfile::tempdir().unwrap();
+    let path_out = dir.path().join("out.txt");
+
+    let mut output = FileOutput::open_atomic(&path_out, FileOutputMode::Create).unwrap();
+    output.write("NEW\n").unwrap();
+    fs::write(&path_out, "GOOD\n").unwrap();
+
+    let result = output.finish();
+    drop(output);
+
+    assert!(
+        matches!(&result, Err(FrameworkError::Output(error)) if error.kind() == std::io::ErrorKind::AlreadyExists),
+        "{result:?}"
+    );
+    assert_eq!("GOOD\n", fs::read_to_string(&path_out).unwrap());
+    assert_eq!(["out.txt"], file_names(dir.path()).as_slice());
+}
+
+#[test]
+fn finish_atomic_create_creates_destination() {
+    let dir = tempfile::tempdir().unwrap();
+    let path_out = dir.path().join("out.txt");
+
+    let mut output = FileOutput::open_atomic(&path_out, FileOutputMode::Create).unwrap();
+    output.write("NEW\n").unwrap();
+    output.finish().unwrap();
+    drop(output);
+
+    assert_eq!("NEW\n", fs::read_to_string(&path_out).unwrap());
+    assert_eq!(["out.txt"], file_names(dir.path()).as_slice());
+}
+
+fn file_names(dir: &std::path::Path) -> Vec<String> {
+    let mut file_names = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect::<Vec<_>>();
+    file_names.sort();
+
+    file_names
+}
@@ -0,0 +1,88 @@
+use assoc_type_params::{FrameworkError, InputExt, MemoryInput, MemoryOutput};
+
+use crate::common::cmd_ctx;
+
+mod common;
+
+#[test]
+fn read_parsed_trims_the_trailing_newline() {
+    let mut input = MemoryInput::new(["42", "7\r\n"]);
+
+    assert_eq!(42, input.read_parsed::<u32>().unwrap());
+    assert_eq!(7, input.read_parsed::<u32>().unwrap());
+}
+
+#[test]
+fn read_parsed_error_keeps_the_input_text() {
+    let mut input = MemoryInput::new(["abc"]);
+
+    let result = input.read_parsed::<u32>();
+
+    match result {
+        Err(FrameworkError::Parse { input, error }) => {
+            assert_eq!("abc", input);
+            assert_eq!("invalid digit found in string", error.to_string());
+        }
+        _ => panic!("Expected `FrameworkError::Parse`, got: {result:?}"),
+    }
+}
+
+#[test]
+fn read_parsed_returns_end_of_input() {
+    let result = MemoryInput::new(Vec::<String>::new()).read_parsed::<u32>();
+
+    assert!(
+        matches!(result, Err(FrameworkError::EndOfInput)),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn read_parsed_reprompt_reprompts_until_valid() {
+    let mut input = MemoryInput::new(["", "abc", "42"]);
+    let mut output = MemoryOutput::new();
+
+    let age = input
+        .read_parsed_reprompt::<u32, _>(&mut output, "Age: ")
+        .unwrap();
+
+    assert_eq!(42, age);
+    assert_eq!(
+        "Age: Invalid input ``: cannot parse integer from empty string\n\
+         Age: Invalid input `abc`: invalid digit found in string\n\
+         Age: ",
+        output.transcript()
+    );
+    assert_eq!([1, 3, 5], output.flushes());
+}
+
+#[test]
+fn read_parsed_reprompt_stops_at_end_of_input() {
+    let mut input = MemoryInput::new(["abc"]);
+    let mut output = MemoryOutput::new();
+
+    let result = input.read_parsed_reprompt::<u32, _>(&mut output, "Age: ");
+
+    assert!(
+        matches!(result, Err(FrameworkError::EndOfInput)),
+        "{result:?}"
+    );
+    assert_eq!(
+        "Age: Invalid input `abc`: invalid digit found in string\nAge: ",
+        output.transcript()
+    );
+}
+
+#[test]
+fn prompt_parsed_reprompts_until_valid() {
+    let mut cmd_ctx = cmd_ctx(["-1", "3"]);
+
+    let count = cmd_ctx.prompt_parsed::<u8>("Count: ").unwrap();
+
+    assert_eq!(3, count);
+    assert_eq!(
+        "Count: Invalid input `-1`: invalid digit found in string\nCount: ",
+        cmd_ctx.output.transcript()
+    );
+    assert_eq!([1, 3], cmd_ctx.output.flushes());
+}
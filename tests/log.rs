@@ -0,0 +1,80 @@
+use std::fs;
+
+use assoc_type_params::{FileLog, Level, Log, run};
+
+use crate::common::{WorkLogic, cmd_ctx_builder};
+
+mod common;
+
+/// `Log` that records each span entered and exited, and each event.
+#[derive(Default)]
+struct SpanLog {
+    records: Vec<String>,
+}
+
+impl Log for SpanLog {
+    fn event(&mut self, level: Level, message: &str) {
+        self.records.push(format!("{level} {message}"));
+    }
+
+    fn span_enter(&mut self, name: &'static str) {
+        self.records.push(format!("enter {name}"));
+    }
+
+    fn span_exit(&mut self, name: &'static str) {
+        self.records.push(format!("exit {name}"));
+    }
+}
+
+#[test]
+fn file_log_writes_level_spans_and_message() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.log");
+    fs::write(&path, "earlier\n").unwrap();
+
+    let mut file_log = FileLog::open(&path, Level::Info).unwrap();
+    file_log.event(Level::Info, "starting");
+    file_log.in_span("run", |file_log| {
+        file_log.in_span("read", |file_log| {
+            file_log.event(Level::Warn, "read timed out");
+            file_log.event(Level::Debug, "not written");
+        });
+        file_log.event(Level::Error, "logic failed");
+    });
+    drop(file_log);
+
+    assert_eq!(
+        "earlier\n\
+         INFO starting\n\
+         WARN run:read: read timed out\n\
+         ERROR run: logic failed\n",
+        fs::read_to_string(&path).unwrap()
+    );
+}
+
+#[test]
+fn run_records_run_read_work_and_write_spans() {
+    let mut cmd_ctx = cmd_ctx_builder(["hi"])
+        .with_logger(SpanLog::default())
+        .build();
+
+    let result = run(&mut cmd_ctx, &mut WorkLogic);
+
+    assert!(matches!(result, Ok(123)), "{result:?}");
+    assert_eq!(
+        [
+            "enter run",
+            "enter write",
+            "exit write",
+            "enter read",
+            "exit read",
+            "DEBUG read 3 bytes",
+            "enter work",
+            "exit work",
+            "enter write",
+            "exit write",
+            "exit run",
+        ],
+        cmd_ctx.logger.records.as_slice()
+    );
+}
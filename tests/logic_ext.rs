@@ -0,0 +1,193 @@
+use std::{
+    cell::Cell,
+    fmt::{self, Display},
+    rc::Rc,
+};
+
+use assoc_type_params::{Logic, LogicError, LogicExt};
+
+/// Returns its value, counting how many times it is run.
+struct Counted {
+    value: u32,
+    runs: Rc<Cell<u32>>,
+}
+
+impl Counted {
+    fn new(value: u32) -> (Self, Rc<Cell<u32>>) {
+        let runs = Rc::new(Cell::new(0));
+        let counted = Self {
+            value,
+            runs: Rc::clone(&runs),
+        };
+
+        (counted, runs)
+    }
+}
+
+impl Logic for Counted {
+    type Error = LogicError;
+    type ReturnType = u32;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        self.runs.set(self.runs.get() + 1);
+        Ok(self.value)
+    }
+}
+
+/// Fails with a `LogicError`.
+struct Fail(&'static str);
+impl Logic for Fail {
+    type Error = LogicError;
+    type ReturnType = u32;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        Err(LogicError(String::from(self.0)))
+    }
+}
+
+/// Error type that neither converts into nor from `LogicError`.
+#[derive(Debug)]
+struct NetworkError;
+
+impl std::error::Error for NetworkError {}
+
+impl Display for NetworkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "network unreachable".fmt(f)
+    }
+}
+
+/// Fails with a `NetworkError`.
+struct Fetch;
+impl Logic for Fetch {
+    type Error = NetworkError;
+    type ReturnType = u32;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        Err(NetworkError)
+    }
+}
+
+#[derive(Debug)]
+enum AppError {
+    Logic(LogicError),
+    Network(NetworkError),
+}
+
+impl std::error::Error for AppError {}
+
+impl Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Logic(error) => write!(f, "logic: {error}"),
+            AppError::Network(error) => write!(f, "network: {error}"),
+        }
+    }
+}
+
+impl From<LogicError> for AppError {
+    fn from(error: LogicError) -> Self {
+        Self::Logic(error)
+    }
+}
+
+impl From<NetworkError> for AppError {
+    fn from(error: NetworkError) -> Self {
+        Self::Network(error)
+    }
+}
+
+#[test]
+fn then_runs_both_and_returns_next_value() {
+    let (first, first_runs) = Counted::new(1);
+    let (next, next_runs) = Counted::new(2);
+
+    let result = first.then(next).do_work();
+
+    assert!(matches!(result, Ok(2)), "{result:?}");
+    assert_eq!(1, first_runs.get());
+    assert_eq!(1, next_runs.get());
+}
+
+#[test]
+fn then_short_circuits_on_first_error() {
+    let (next, next_runs) = Counted::new(2);
+
+    let result = Fail("first").then(next).do_work();
+
+    match result {
+        Err(LogicError(message)) => assert_eq!("first", message),
+        Ok(_) => panic!("Expected `LogicError`, got: {result:?}"),
+    }
+    assert_eq!(0, next_runs.get());
+}
+
+#[test]
+fn then_converts_first_error_through_from() {
+    let (next, next_runs) = Counted::new(2);
+    let next = next.err_into::<AppError>();
+
+    let result = Fail("first").then(next).do_work();
+
+    assert!(matches!(result, Err(AppError::Logic(_))), "{result:?}");
+    assert_eq!(0, next_runs.get());
+}
+
+#[test]
+fn and_then_passes_value_to_next_logic() {
+    let (first, _first_runs) = Counted::new(21);
+
+    let result = first.and_then(|value| Counted::new(value * 2).0).do_work();
+
+    assert!(matches!(result, Ok(42)), "{result:?}");
+}
+
+#[test]
+fn and_then_short_circuits_on_first_error() {
+    let mut called = false;
+
+    let result = Fail("first")
+        .and_then(|value| {
+            called = true;
+            Counted::new(value).0
+        })
+        .do_work();
+
+    assert!(matches!(result, Err(LogicError(_))), "{result:?}");
+    assert!(!called);
+}
+
+#[test]
+fn map_and_map_err_transform_the_result() {
+    let (counted, _runs) = Counted::new(2);
+
+    let value = counted.map(|value| value.to_string()).do_work();
+    let error = Fail("x")
+        .map_err(|error| LogicError(format!("wrapped {}", error.0)))
+        .do_work();
+
+    assert_eq!("2", value.unwrap());
+    match error {
+        Err(LogicError(message)) => assert_eq!("wrapped x", message),
+        Ok(_) => panic!("Expected `LogicError`, got: {error:?}"),
+    }
+}
+
+#[test]
+fn err_into_merges_error_types_into_app_error() {
+    let (counted, _runs) = Counted::new(1);
+    let mut fetch_fails = counted.err_into::<AppError>().then(Fetch.err_into());
+    let mut logic_fails = Fail("x").err_into::<AppError>().then(Fetch.err_into());
+
+    let fetch_fails = fetch_fails.do_work();
+    let logic_fails = logic_fails.do_work();
+
+    assert!(
+        matches!(fetch_fails, Err(AppError::Network(NetworkError))),
+        "{fetch_fails:?}"
+    );
+    assert!(
+        matches!(logic_fails, Err(AppError::Logic(_))),
+        "{logic_fails:?}"
+    );
+}
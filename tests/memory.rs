@@ -0,0 +1,91 @@
+use assoc_type_params::{
+    CmdCtx, CtxLogic, FrameworkError, LogicError, MemoryEndpoint, Output, run, run_ctx,
+};
+
+use crate::common::{WorkLogic, cmd_ctx};
+
+mod common;
+
+#[test]
+fn run_writes_transcript_and_returns_logic_value() {
+    let mut cmd_ctx = cmd_ctx(["hi"]);
+
+    let result = run(&mut cmd_ctx, &mut WorkLogic);
+
+    assert!(matches!(result, Ok(123)), "{result:?}");
+    assert_eq!(
+        "Enter some input:\nYou entered: hi\n",
+        cmd_ctx.output.transcript()
+    );
+    assert!(cmd_ctx.input.remaining().is_empty());
+}
+
+#[test]
+fn run_returns_end_of_input_when_input_is_empty() {
+    let mut cmd_ctx = cmd_ctx([]);
+
+    let result = run(&mut cmd_ctx, &mut WorkLogic);
+
+    assert!(
+        matches!(result, Err(FrameworkError::EndOfInput)),
+        "{result:?}"
+    );
+    assert_eq!("Enter some input:\n", cmd_ctx.output.transcript());
+}
+
+#[test]
+fn memory_output_records_each_write() {
+    let mut cmd_ctx = cmd_ctx(["a", "b\n"]);
+
+    let _ = run(&mut cmd_ctx, &mut WorkLogic);
+
+    assert_eq!(
+        ["Enter some input:\n", "You entered: ", "a\n"],
+        cmd_ctx.output.writes()
+    );
+    assert_eq!(1, cmd_ctx.input.remaining().len());
+}
+
+/// Doubles the number it receives, recording the number and writing a line.
+#[derive(Default)]
+struct DoubleLogic {
+    received: Option<u32>,
+}
+
+impl CtxLogic<MemoryEndpoint> for DoubleLogic {
+    type Error = LogicError;
+    type Input = u32;
+    type ReturnType = u32;
+
+    fn do_work(
+        &mut self,
+        cmd_ctx: &mut CmdCtx<MemoryEndpoint>,
+        input: Self::Input,
+    ) -> Result<Self::ReturnType, Self::Error> {
+        self.received = Some(input);
+        cmd_ctx
+            .output
+            .write(&format!("Doubling {input}\n"))
+            .map_err(|error| LogicError(error.to_string()))?;
+
+        Ok(input * 2)
+    }
+}
+
+#[test]
+fn run_ctx_reprompts_then_passes_parsed_input_to_logic() {
+    let mut cmd_ctx = cmd_ctx(["abc", "21"]);
+    let mut logic = DoubleLogic::default();
+
+    let result = run_ctx(&mut cmd_ctx, &mut logic);
+
+    assert!(matches!(result, Ok(42)), "{result:?}");
+    assert_eq!(Some(21), logic.received);
+    assert_eq!(
+        "Enter some input:\n\
+         Invalid input `abc`: invalid digit found in string\n\
+         Enter some input:\n\
+         Doubling 21\n",
+        cmd_ctx.output.transcript()
+    );
+}
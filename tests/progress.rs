@@ -0,0 +1,81 @@
+use assoc_type_params::{
+    CmdCtx, MemoryEndpoint, MemoryOutput, OutputFormat, Progress, ProgressStyle,
+};
+use serde::Deserialize;
+
+mod common;
+
+fn cmd_ctx(output_format: OutputFormat) -> CmdCtx<MemoryEndpoint> {
+    common::cmd_ctx_builder([])
+        .with_output_format(output_format)
+        .build_as::<MemoryEndpoint>()
+}
+
+fn report_progress(cmd_ctx: &mut CmdCtx<MemoryEndpoint>) {
+    let mut progress = cmd_ctx.progress(Some(2));
+    progress.step(1, "a").unwrap();
+    progress.step(1, "b").unwrap();
+    progress.finish().unwrap();
+}
+
+#[test]
+fn text_progress_is_written_as_lines_when_not_a_terminal() {
+    let mut cmd_ctx = cmd_ctx(OutputFormat::Text);
+
+    report_progress(&mut cmd_ctx);
+
+    assert_eq!("1/2  50% a\n2/2 100% b\n", cmd_ctx.output.transcript());
+}
+
+#[test]
+fn bar_progress_is_redrawn_in_place() {
+    let mut output = MemoryOutput::new();
+
+    let mut progress = Progress::new(&mut output, ProgressStyle::Bar, Some(2));
+    progress.inc(1).unwrap();
+    progress.finish().unwrap();
+
+    assert_eq!(
+        format!("\r[{}{}] 1/2  50%\x1b[K\n", "#".repeat(15), "-".repeat(15)),
+        output.transcript()
+    );
+}
+
+#[test]
+fn json_progress_and_value_are_one_document_per_line() {
+    let mut cmd_ctx = cmd_ctx(OutputFormat::Json);
+
+    report_progress(&mut cmd_ctx);
+    cmd_ctx.present(&123u8).unwrap();
+
+    let documents = cmd_ctx
+        .output
+        .transcript()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(4, documents.len());
+    assert_eq!("progress", documents[0]["event"]);
+    assert_eq!("b", documents[1]["message"]);
+    assert_eq!("finish", documents[2]["event"]);
+    assert_eq!(123, documents[3]);
+}
+
+#[test]
+fn yaml_progress_and_value_are_a_yaml_stream() {
+    let mut cmd_ctx = cmd_ctx(OutputFormat::Yaml);
+
+    report_progress(&mut cmd_ctx);
+    cmd_ctx.present(&123u8).unwrap();
+
+    let transcript = cmd_ctx.output.transcript();
+    let documents = serde_yaml::Deserializer::from_str(&transcript)
+        .map(serde_yaml::Value::deserialize)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(4, documents.len(), "{transcript}");
+    assert_eq!(documents[0]["event"], "progress");
+    assert_eq!(documents[1]["message"], "b");
+    assert_eq!(documents[2]["event"], "finish");
+    assert_eq!(documents[3], 123);
+}
@@ -0,0 +1,159 @@
+use assoc_type_params::FrameworkError;
+
+use crate::common::cmd_ctx;
+
+mod common;
+
+#[test]
+fn confirm_accepts_yes_and_no_in_any_case() {
+    let mut cmd_ctx = cmd_ctx(["y", "YES", "n", "No"]);
+
+    assert!(cmd_ctx.confirm("Continue?", false).unwrap());
+    assert!(cmd_ctx.confirm("Continue?", false).unwrap());
+    assert!(!cmd_ctx.confirm("Continue?", true).unwrap());
+    assert!(!cmd_ctx.confirm("Continue?", true).unwrap());
+}
+
+#[test]
+fn confirm_returns_default_for_empty_line() {
+    let mut cmd_ctx = cmd_ctx(["", ""]);
+
+    assert!(cmd_ctx.confirm("Continue?", true).unwrap());
+    assert!(!cmd_ctx.confirm("Continue?", false).unwrap());
+    assert_eq!(
+        "Continue? [Y/n] Continue? [y/N] ",
+        cmd_ctx.output.transcript()
+    );
+}
+
+#[test]
+fn confirm_asks_again_for_invalid_answer() {
+    let mut cmd_ctx = cmd_ctx(["maybe", "y"]);
+
+    assert!(cmd_ctx.confirm("Continue?", false).unwrap());
+    assert_eq!(
+        "Continue? [y/N] Please enter `y` or `n`.\nContinue? [y/N] ",
+        cmd_ctx.output.transcript()
+    );
+}
+
+#[test]
+fn confirm_returns_end_of_input() {
+    let mut cmd_ctx = cmd_ctx([]);
+
+    let result = cmd_ctx.confirm("Continue?", true);
+
+    assert!(
+        matches!(result, Err(FrameworkError::EndOfInput)),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn select_returns_index_of_chosen_option() {
+    let mut cmd_ctx = cmd_ctx(["0", "x", "2"]);
+
+    let index = cmd_ctx.select("Pick", &["a", "b", "c"]).unwrap();
+
+    assert_eq!(1, index);
+    assert_eq!(
+        "Pick\n\
+         \x20 1) a\n\
+         \x20 2) b\n\
+         \x20 3) c\n\
+         Enter a number (1-3): `0` is not an option, enter a number from 1 to 3.\n\
+         Enter a number (1-3): `x` is not an option, enter a number from 1 to 3.\n\
+         Enter a number (1-3): ",
+        cmd_ctx.output.transcript()
+    );
+}
+
+#[test]
+fn select_aligns_option_numbers() {
+    let options = (1..=10).map(|n| format!("option {n}")).collect::<Vec<_>>();
+    let mut cmd_ctx = cmd_ctx(["10"]);
+
+    let index = cmd_ctx.select("Pick", &options).unwrap();
+
+    assert_eq!(9, index);
+    let transcript = cmd_ctx.output.transcript();
+    assert!(transcript.contains("\n   1) option 1\n"), "{transcript}");
+    assert!(transcript.contains("\n  10) option 10\n"), "{transcript}");
+}
+
+#[test]
+fn select_returns_usage_error_without_options() {
+    let mut cmd_ctx = cmd_ctx(["1"]);
+
+    let result = cmd_ctx.select::<&str>("Pick", &[]);
+
+    assert!(
+        matches!(result, Err(FrameworkError::Usage(_))),
+        "{result:?}"
+    );
+    assert_eq!("", cmd_ctx.output.transcript());
+}
+
+#[test]
+fn multi_select_returns_sorted_unique_indices() {
+    let mut cmd_ctx = cmd_ctx(["3, 1 1"]);
+
+    let indices = cmd_ctx.multi_select("Pick", &["a", "b", "c"]).unwrap();
+
+    assert_eq!(vec![0, 2], indices);
+}
+
+#[test]
+fn multi_select_returns_none_for_empty_line() {
+    let mut cmd_ctx = cmd_ctx([""]);
+
+    let indices = cmd_ctx.multi_select("Pick", &["a", "b", "c"]).unwrap();
+
+    assert!(indices.is_empty());
+}
+
+#[test]
+fn multi_select_asks_again_for_invalid_number() {
+    let mut cmd_ctx = cmd_ctx(["1,9", "2"]);
+
+    let indices = cmd_ctx.multi_select("Pick", &["a", "b", "c"]).unwrap();
+
+    assert_eq!(vec![1], indices);
+    let transcript = cmd_ctx.output.transcript();
+    assert!(
+        transcript.contains("`9` is not an option, enter a number from 1 to 3.\n"),
+        "{transcript}"
+    );
+}
+
+#[test]
+fn text_asks_again_until_validators_accept() {
+    let non_empty = |text: &str| match text.is_empty() {
+        true => Err(String::from("must not be empty")),
+        false => Ok(()),
+    };
+    let short = |text: &str| match text.len() > 5 {
+        true => Err(String::from("must be at most 5 characters")),
+        false => Ok(()),
+    };
+    let mut cmd_ctx = cmd_ctx(["", "alexander", "alex"]);
+
+    let text = cmd_ctx.text("Name", &[&non_empty, &short]).unwrap();
+
+    assert_eq!("alex", text);
+    assert_eq!(
+        "Name: must not be empty\n\
+         Name: must be at most 5 characters\n\
+         Name: ",
+        cmd_ctx.output.transcript()
+    );
+}
+
+#[test]
+fn text_without_validators_returns_line() {
+    let mut cmd_ctx = cmd_ctx(["  spaced  \r"]);
+
+    let text = cmd_ctx.text("Name", &[]).unwrap();
+
+    assert_eq!("  spaced  ", text);
+}
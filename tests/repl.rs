@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use assoc_type_params::{
+    CmdCtx, CmdCtxBuilder, Commands, CtxLogic, FrameworkError, LogicError, MemoryEndpoint,
+    MemoryOutput, Repl, ScriptedEndpoint, ScriptedInput, Timeouts,
+};
+
+use crate::common::{Greet, cmd_ctx};
+
+mod common;
+
+struct Double;
+impl CtxLogic<MemoryEndpoint> for Double {
+    type Error = LogicError;
+    type Input = u32;
+    type ReturnType = u32;
+
+    fn do_work(
+        &mut self,
+        _cmd_ctx: &mut CmdCtx<MemoryEndpoint>,
+        input: Self::Input,
+    ) -> Result<Self::ReturnType, Self::Error> {
+        Ok(input * 2)
+    }
+}
+
+fn commands() -> Commands<MemoryEndpoint> {
+    Commands::new()
+        .with_command("greet", "Says hello.", Greet)
+        .with_ctx_command("double", "Doubles a number.", Double)
+}
+
+#[test]
+fn dispatches_lines_until_end_of_input() {
+    let mut cmd_ctx = cmd_ctx(["greet", "", "double 21"]);
+
+    Repl::new(commands()).run(&mut cmd_ctx).unwrap();
+
+    assert_eq!("> hello\n> > 42\n> ", cmd_ctx.output.transcript());
+}
+
+#[test]
+fn quit_ends_the_loop() {
+    let mut cmd_ctx = cmd_ctx(["quit", "greet"]);
+
+    Repl::new(commands())
+        .with_prompt("$ ")
+        .run(&mut cmd_ctx)
+        .unwrap();
+
+    assert_eq!("$ ", cmd_ctx.output.transcript());
+    assert_eq!(1, cmd_ctx.input.remaining().len());
+}
+
+#[test]
+fn errors_and_unknown_commands_do_not_end_the_loop() {
+    let mut cmd_ctx = cmd_ctx(["double abc", "nope", "greet"]);
+
+    Repl::new(commands()).run(&mut cmd_ctx).unwrap();
+
+    let transcript = cmd_ctx.output.transcript();
+    assert!(
+        transcript.starts_with("> error: Parse error: `abc`\n"),
+        "{transcript}"
+    );
+    assert!(
+        transcript
+            .ends_with("> Unknown command `nope`. Enter `help` to list commands.\n> hello\n> "),
+        "{transcript}"
+    );
+}
+
+#[test]
+fn help_lists_commands_and_shows_command_help() {
+    let mut cmd_ctx = cmd_ctx(["help", "help double", "help nope"]);
+
+    Repl::new(commands()).run(&mut cmd_ctx).unwrap();
+
+    assert_eq!(
+        "> Commands:\n\
+         \x20 double  Doubles a number.\n\
+         \x20 greet   Says hello.\n\
+         \x20 help    Lists commands, or shows help for a command.\n\
+         \x20 quit    Exits.\n\
+         > double: Doubles a number.\n\
+         > Unknown command `nope`.\n\
+         > ",
+        cmd_ctx.output.transcript()
+    );
+}
+
+#[test]
+fn cancelled_token_stops_the_loop() {
+    let mut cmd_ctx = cmd_ctx(["greet"]);
+    cmd_ctx.cancel_token().cancel();
+
+    let result = Repl::new(commands()).run(&mut cmd_ctx);
+
+    assert!(
+        matches!(result, Err(FrameworkError::Interrupted)),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn prompt_is_flushed_before_each_read() {
+    let mut cmd_ctx = cmd_ctx(["greet", ""]);
+
+    Repl::new(commands()).run(&mut cmd_ctx).unwrap();
+
+    assert_eq!(["> ", "hello\n", "> ", "> "], cmd_ctx.output.writes());
+    assert_eq!([1, 3, 4], cmd_ctx.output.flushes());
+}
+
+#[test]
+fn read_timeout_uses_the_read_default() {
+    let mut cmd_ctx = CmdCtxBuilder::new()
+        .with_input(ScriptedInput::new([(Duration::from_secs(5), "greet")]))
+        .with_output(MemoryOutput::new())
+        .with_error::<FrameworkError>()
+        .with_timeouts(
+            Timeouts::new()
+                .with_read(Duration::from_millis(20))
+                .with_read_default("quit"),
+        )
+        .build_as::<ScriptedEndpoint>();
+    let commands = Commands::new().with_command("greet", "Says hello.", Greet);
+
+    Repl::new(commands).run(&mut cmd_ctx).unwrap();
+
+    assert_eq!("> quit\n", cmd_ctx.output.transcript());
+}
@@ -0,0 +1,91 @@
+use assoc_type_params::{FrameworkError, MemoryEndpoint, ResourceError, Resources};
+
+mod common;
+
+#[derive(Debug, PartialEq)]
+struct Counter(u32);
+
+#[test]
+fn insert_returns_previous_resource() {
+    let mut resources = Resources::new();
+
+    assert_eq!(None, resources.insert(Counter(1)));
+    assert_eq!(Some(Counter(1)), resources.insert(Counter(2)));
+    assert!(resources.contains::<Counter>());
+    assert_eq!(2, resources.borrow::<Counter>().unwrap().0);
+}
+
+#[test]
+fn remove_returns_resource() {
+    let mut resources = Resources::new();
+    resources.insert(Counter(1));
+
+    assert_eq!(Some(Counter(1)), resources.remove::<Counter>());
+    assert_eq!(None, resources.remove::<Counter>());
+    assert!(!resources.contains::<Counter>());
+}
+
+#[test]
+fn borrow_returns_missing_when_not_inserted() {
+    let resources = Resources::new();
+
+    let result = resources.borrow::<Counter>().map(|_| ());
+
+    assert!(
+        matches!(
+            &result,
+            Err(FrameworkError::Resource(ResourceError::Missing { type_name }))
+                if type_name.ends_with("Counter")
+        ),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn borrow_returns_borrowed_mut_while_mutably_borrowed() {
+    let mut resources = Resources::new();
+    resources.insert(Counter(1));
+
+    let counter = resources.borrow_mut::<Counter>().unwrap();
+    let result = resources.borrow::<Counter>().map(|_| ());
+    drop(counter);
+
+    assert!(
+        matches!(
+            result,
+            Err(FrameworkError::Resource(ResourceError::BorrowedMut { .. }))
+        ),
+        "{result:?}"
+    );
+    assert!(resources.borrow::<Counter>().is_ok());
+}
+
+#[test]
+fn borrow_mut_returns_borrowed_while_borrowed() {
+    let mut resources = Resources::new();
+    resources.insert(Counter(1));
+
+    let counter_0 = resources.borrow::<Counter>().unwrap();
+    let counter_1 = resources.borrow::<Counter>().unwrap();
+    let result = resources.borrow_mut::<Counter>().map(|_| ());
+    drop((counter_0, counter_1));
+
+    assert!(
+        matches!(
+            result,
+            Err(FrameworkError::Resource(ResourceError::Borrowed { .. }))
+        ),
+        "{result:?}"
+    );
+}
+
+#[test]
+fn cmd_ctx_resources_are_shared_with_builder() {
+    let cmd_ctx = common::cmd_ctx_builder([])
+        .with_resource(Counter(1))
+        .build_as::<MemoryEndpoint>();
+
+    cmd_ctx.borrow_mut::<Counter>().unwrap().0 += 1;
+
+    assert_eq!(Counter(2), *cmd_ctx.borrow::<Counter>().unwrap());
+}
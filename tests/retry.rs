@@ -0,0 +1,189 @@
+use std::time::{Duration, Instant};
+
+use assoc_type_params::{
+    CmdCtx, FrameworkError, Logic, LogicError, LogicExt, MemoryEndpoint, OutputFormat, ToExitCode,
+    sysexits::{EX_SOFTWARE, EX_TEMPFAIL},
+};
+
+use serde::Deserialize;
+
+use crate::common::{cmd_ctx, cmd_ctx_builder};
+
+mod common;
+
+/// Fails until it has been run `failures` times.
+struct FlakyLogic {
+    failures: u32,
+    runs: u32,
+}
+
+impl Logic for FlakyLogic {
+    type Error = LogicError;
+    type ReturnType = u32;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        self.runs += 1;
+        if self.runs <= self.failures {
+            Err(LogicError(format!("failure {}", self.runs)))
+        } else {
+            Ok(self.runs)
+        }
+    }
+}
+
+fn flaky(failures: u32) -> FlakyLogic {
+    FlakyLogic { failures, runs: 0 }
+}
+
+#[test]
+fn retries_until_logic_succeeds() {
+    let mut cmd_ctx = cmd_ctx([]);
+    let mut retry = flaky(2).retry().with_backoff(Duration::from_millis(1));
+
+    let result = retry.run(&mut cmd_ctx);
+
+    assert!(matches!(result, Ok(3)), "{result:?}");
+    let writes = cmd_ctx.output.writes();
+    assert_eq!(2, writes.len(), "{writes:?}");
+    assert!(
+        writes[0].starts_with("Attempt 1 of 3 failed: failure 1. Retrying in "),
+        "{writes:?}"
+    );
+    assert!(
+        writes[1].starts_with("Attempt 2 of 3 failed: failure 2. Retrying in "),
+        "{writes:?}"
+    );
+}
+
+#[test]
+fn returns_retry_error_with_history_when_attempts_run_out() {
+    let mut cmd_ctx = cmd_ctx([]);
+    let mut retry = flaky(5)
+        .retry()
+        .with_attempts(2)
+        .with_backoff(Duration::from_millis(1));
+
+    let result = retry.run(&mut cmd_ctx);
+
+    let Err(error) = result else {
+        panic!("expected error, got {result:?}");
+    };
+    let FrameworkError::Retry { history, error: _ } = &error else {
+        panic!("expected retry error, got {error:?}");
+    };
+    let history = history.lines().collect::<Vec<_>>();
+    assert_eq!(2, history.len(), "{history:?}");
+    assert!(
+        history[0].starts_with("attempt 1 failed after ")
+            && history[0].contains(": failure 1, retried after "),
+        "{history:?}"
+    );
+    assert!(
+        history[1].starts_with("attempt 2 failed after ") && history[1].ends_with(": failure 2"),
+        "{history:?}"
+    );
+    assert_eq!(EX_TEMPFAIL, error.exit_code());
+    assert_eq!(2, retry.logic().runs);
+}
+
+#[test]
+fn returns_error_that_is_not_retryable_as_is() {
+    let mut cmd_ctx = cmd_ctx([]);
+    let mut retry = flaky(5).retry().retry_if(|_: &LogicError| false);
+
+    let result = retry.run(&mut cmd_ctx);
+
+    let Err(error) = result else {
+        panic!("expected error, got {result:?}");
+    };
+    assert!(
+        matches!(&error, FrameworkError::Logic(LogicError(message)) if message == "failure 1"),
+        "{error:?}"
+    );
+    assert_eq!(EX_SOFTWARE, error.exit_code());
+    assert_eq!("", cmd_ctx.output.transcript());
+    assert_eq!(1, retry.logic().runs);
+}
+
+#[test]
+fn stops_backoff_when_cancelled() {
+    let mut cmd_ctx = cmd_ctx([]);
+    cmd_ctx.cancel_token().cancel();
+    let mut retry = flaky(5).retry().with_backoff(Duration::from_secs(5));
+
+    let result = retry.run(&mut cmd_ctx);
+
+    assert!(
+        matches!(result, Err(FrameworkError::Interrupted)),
+        "{result:?}"
+    );
+    assert_eq!(1, retry.logic().runs);
+}
+
+fn cmd_ctx_with_format(output_format: OutputFormat) -> CmdCtx<MemoryEndpoint> {
+    cmd_ctx_builder([])
+        .with_output_format(output_format)
+        .build_as::<MemoryEndpoint>()
+}
+
+#[test]
+fn retries_are_json_events_in_json_output_format() {
+    let mut cmd_ctx = cmd_ctx_with_format(OutputFormat::Json);
+    let mut retry = flaky(2).retry().with_backoff(Duration::from_millis(1));
+
+    let result = retry.run(&mut cmd_ctx);
+    cmd_ctx.present(&result.unwrap()).unwrap();
+
+    let documents = cmd_ctx
+        .output
+        .transcript()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(3, documents.len());
+    assert_eq!("retry", documents[0]["event"]);
+    assert_eq!(1, documents[0]["attempt"]);
+    assert_eq!(3, documents[0]["attempts"]);
+    assert_eq!("failure 1", documents[0]["error"]);
+    assert_eq!("failure 2", documents[1]["error"]);
+    assert_eq!(3, documents[2]);
+}
+
+#[test]
+fn retries_are_yaml_documents_in_yaml_output_format() {
+    let mut cmd_ctx = cmd_ctx_with_format(OutputFormat::Yaml);
+    let mut retry = flaky(1).retry().with_backoff(Duration::from_millis(1));
+
+    let result = retry.run(&mut cmd_ctx);
+    cmd_ctx.present(&result.unwrap()).unwrap();
+
+    let transcript = cmd_ctx.output.transcript();
+    let documents = serde_yaml::Deserializer::from_str(&transcript)
+        .map(serde_yaml::Value::deserialize)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(2, documents.len(), "{transcript}");
+    assert_eq!(documents[0]["event"], "retry");
+    assert_eq!(documents[0]["error"], "failure 1");
+    assert_eq!(documents[1], 2);
+}
+
+#[test]
+fn initial_backoff_is_capped_at_max_backoff() {
+    let mut cmd_ctx = cmd_ctx([]);
+    let mut retry = flaky(1)
+        .retry()
+        .with_backoff(Duration::from_secs(60))
+        .with_backoff_max(Duration::from_millis(1));
+    let start = Instant::now();
+
+    let result = retry.run(&mut cmd_ctx);
+
+    assert!(matches!(result, Ok(2)), "{result:?}");
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(
+        cmd_ctx.output.writes()[0].ends_with("Retrying in 0ms.\n"),
+        "{:?}",
+        cmd_ctx.output.writes()
+    );
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use assoc_type_params::{
+    CmdCtxBuilder, FrameworkError, MemoryEndpoint, MemoryInput, MemoryOutput, ScriptedEndpoint,
+    ScriptedInput, TimeoutPhase, Timeouts,
+};
+
+#[test]
+fn prompt_secret_writes_prompt_and_reads_secret() {
+    let mut cmd_ctx = CmdCtxBuilder::new()
+        .with_input(MemoryInput::new(["hunter2"]))
+        .with_output(MemoryOutput::new())
+        .with_error::<FrameworkError>()
+        .build_as::<MemoryEndpoint>();
+
+    let secret = cmd_ctx.prompt_secret("Password: ").unwrap();
+
+    assert_eq!("hunter2", secret.expose());
+    assert_eq!("Secret(..)", format!("{secret:?}"));
+    assert_eq!("Password: ", cmd_ctx.output.transcript());
+}
+
+#[test]
+fn prompt_secret_times_out_without_using_read_default() {
+    let mut cmd_ctx = CmdCtxBuilder::new()
+        .with_input(ScriptedInput::new([(Duration::from_secs(5), "hunter2")]))
+        .with_output(MemoryOutput::new())
+        .with_error::<FrameworkError>()
+        .with_timeouts(
+            Timeouts::new()
+                .with_read(Duration::from_millis(20))
+                .with_read_default("dflt"),
+        )
+        .build_as::<ScriptedEndpoint>();
+
+    let result = cmd_ctx.prompt_secret("Password: ");
+
+    assert!(
+        matches!(result, Err(FrameworkError::Timeout(TimeoutPhase::Read))),
+        "{result:?}"
+    );
+}
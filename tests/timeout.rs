@@ -0,0 +1,206 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use assoc_type_params::{
+    CmdCtx, CmdCtxBuilder, FrameworkError, Input, Level, Log, Logic, LogicError, MemoryOutput,
+    ScriptedEndpoint, ScriptedInput, TimeoutPhase, Timeouts, TypeParams, run,
+};
+
+/// `Log` that records each event with its level.
+#[derive(Default)]
+struct RecordLog {
+    events: Vec<(Level, String)>,
+}
+
+impl Log for RecordLog {
+    fn event(&mut self, level: Level, message: &str) {
+        self.events.push((level, message.to_string()));
+    }
+
+    fn span_enter(&mut self, _name: &'static str) {}
+
+    fn span_exit(&mut self, _name: &'static str) {}
+}
+
+type RecordEndpoint = TypeParams<FrameworkError, ScriptedInput, MemoryOutput, (), RecordLog>;
+
+struct WorkLogic {
+    duration: Duration,
+}
+
+impl Logic for WorkLogic {
+    type Error = LogicError;
+    type ReturnType = u8;
+
+    fn do_work(&mut self) -> Result<Self::ReturnType, Self::Error> {
+        thread::sleep(self.duration);
+        Ok(123)
+    }
+}
+
+fn cmd_ctx(delay: Duration, timeouts: Timeouts) -> CmdCtx<ScriptedEndpoint> {
+    CmdCtxBuilder::new()
+        .with_input(ScriptedInput::new([(delay, "hi")]))
+        .with_output(MemoryOutput::new())
+        .with_error::<FrameworkError>()
+        .with_timeouts(timeouts)
+        .build_as::<ScriptedEndpoint>()
+}
+
+#[test]
+fn read_line_returns_timeout_when_input_is_slow() {
+    let mut cmd_ctx = cmd_ctx(
+        Duration::from_secs(5),
+        Timeouts::new().with_read(Duration::from_millis(20)),
+    );
+
+    let result = cmd_ctx.read_line();
+
+    assert!(
+        matches!(result, Err(FrameworkError::Timeout(TimeoutPhase::Read))),
+        "{result:?}"
+    );
+    assert_eq!(1, cmd_ctx.input.remaining().len());
+    assert_eq!(None, cmd_ctx.cancel_token().deadline());
+}
+
+#[test]
+fn read_line_falls_back_to_read_default() {
+    let mut cmd_ctx = cmd_ctx(
+        Duration::from_secs(5),
+        Timeouts::new()
+            .with_read(Duration::from_millis(20))
+            .with_read_default("dflt"),
+    );
+
+    let line = cmd_ctx.read_line().unwrap();
+
+    assert_eq!("dflt\n", line);
+    assert_eq!("dflt\n", cmd_ctx.output.transcript());
+}
+
+#[test]
+fn read_line_returns_line_read_before_deadline() {
+    let mut cmd_ctx = cmd_ctx(
+        Duration::from_millis(20),
+        Timeouts::new()
+            .with_read(Duration::from_secs(5))
+            .with_read_default("dflt"),
+    );
+
+    let line = cmd_ctx.read_line().unwrap();
+
+    assert_eq!("hi\n", line);
+    assert_eq!("", cmd_ctx.output.transcript());
+}
+
+/// `Input` that blocks for `delay` on each read, without checking the cancel
+/// token, like piped `Stdin`.
+struct BlockingInput {
+    delay: Duration,
+}
+
+impl Input for BlockingInput {
+    fn read(&mut self) -> Result<String, FrameworkError> {
+        thread::sleep(self.delay);
+        Ok(String::from("hi\n"))
+    }
+}
+
+#[test]
+fn read_line_returns_timeout_after_blocking_read_overruns() {
+    let mut cmd_ctx = CmdCtxBuilder::new()
+        .with_input(BlockingInput {
+            delay: Duration::from_millis(100),
+        })
+        .with_output(MemoryOutput::new())
+        .with_error::<FrameworkError>()
+        .with_timeouts(Timeouts::new().with_read(Duration::from_millis(10)))
+        .build();
+    let start = Instant::now();
+
+    let result = cmd_ctx.read_line();
+
+    assert!(
+        matches!(result, Err(FrameworkError::Timeout(TimeoutPhase::Read))),
+        "{result:?}"
+    );
+    assert!(
+        start.elapsed() >= Duration::from_millis(100),
+        "read should block until the line arrives"
+    );
+}
+
+#[test]
+fn run_returns_timeout_when_input_is_slow() {
+    let mut cmd_ctx = cmd_ctx(
+        Duration::from_secs(5),
+        Timeouts::new().with_read(Duration::from_millis(20)),
+    );
+    let mut logic = WorkLogic {
+        duration: Duration::ZERO,
+    };
+
+    let result = run(&mut cmd_ctx, &mut logic);
+
+    assert!(
+        matches!(result, Err(FrameworkError::Timeout(TimeoutPhase::Read))),
+        "{result:?}"
+    );
+    assert_eq!("Enter some input:\n", cmd_ctx.output.transcript());
+}
+
+#[test]
+fn run_uses_read_default_when_input_is_slow() {
+    let mut cmd_ctx = cmd_ctx(
+        Duration::from_secs(5),
+        Timeouts::new()
+            .with_read(Duration::from_millis(20))
+            .with_read_default("dflt"),
+    );
+    let mut logic = WorkLogic {
+        duration: Duration::ZERO,
+    };
+
+    let result = run(&mut cmd_ctx, &mut logic);
+
+    assert!(matches!(result, Ok(123)), "{result:?}");
+    assert_eq!(
+        "Enter some input:\ndflt\nYou entered: dflt\n",
+        cmd_ctx.output.transcript()
+    );
+}
+
+#[test]
+fn run_keeps_result_of_logic_that_overruns_and_logs_warning() {
+    let mut cmd_ctx = CmdCtxBuilder::new()
+        .with_input(ScriptedInput::new([(Duration::ZERO, "hi")]))
+        .with_output(MemoryOutput::new())
+        .with_error::<FrameworkError>()
+        .with_logger(RecordLog::default())
+        .with_timeouts(Timeouts::new().with_work(Duration::from_millis(1)))
+        .build_as::<RecordEndpoint>();
+    let mut logic = WorkLogic {
+        duration: Duration::from_millis(50),
+    };
+
+    let result = run(&mut cmd_ctx, &mut logic);
+
+    assert!(matches!(result, Ok(123)), "{result:?}");
+    assert_eq!(
+        "Enter some input:\nYou entered: hi\n",
+        cmd_ctx.output.transcript()
+    );
+    assert!(
+        cmd_ctx
+            .logger
+            .events
+            .iter()
+            .any(|(level, message)| *level == Level::Warn
+                && message.ends_with("longer than its 1ms timeout")),
+        "{:?}",
+        cmd_ctx.logger.events
+    );
+}
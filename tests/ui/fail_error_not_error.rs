@@ -0,0 +1,9 @@
+use std::io::{Stdin, Stdout};
+
+use assoc_type_params::TypeParams;
+
+#[derive(TypeParams)]
+#[type_params(error = String, input = Stdin, output = Stdout)]
+struct Endpoint;
+
+fn main() {}
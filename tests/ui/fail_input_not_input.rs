@@ -0,0 +1,9 @@
+use std::io::Stdout;
+
+use assoc_type_params::{FrameworkError, TypeParams};
+
+#[derive(TypeParams)]
+#[type_params(error = FrameworkError, input = u32, output = Stdout)]
+struct Endpoint;
+
+fn main() {}
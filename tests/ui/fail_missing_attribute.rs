@@ -0,0 +1,6 @@
+use assoc_type_params::TypeParams;
+
+#[derive(TypeParams)]
+struct Endpoint;
+
+fn main() {}
@@ -0,0 +1,7 @@
+use assoc_type_params::TypeParams;
+
+#[derive(TypeParams)]
+#[type_params(error = assoc_type_params::FrameworkError, input = std::io::Stdin, output = std::io::Stdout, config = ())]
+struct Endpoint;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use std::io::{Stdin, Stdout};
+
+use assoc_type_params::{FrameworkError, TypeParams};
+
+#[derive(TypeParams)]
+#[type_params(error = FrameworkError, input = Stdin, output = Stdout, logger = String)]
+struct Endpoint;
+
+fn main() {}
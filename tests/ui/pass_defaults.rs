@@ -0,0 +1,23 @@
+use std::io::{Stdin, Stdout};
+
+use assoc_type_params::{DiscardLog, FrameworkError, TypeParams, TypeParamsT};
+
+#[derive(TypeParams)]
+#[type_params(error = FrameworkError, input = Stdin, output = Stdout)]
+struct StdioEndpoint;
+
+fn assert_types<T>()
+where
+    T: TypeParamsT<
+            AppError = FrameworkError,
+            Input = Stdin,
+            Output = Stdout,
+            State = (),
+            Logger = DiscardLog,
+        >,
+{
+}
+
+fn main() {
+    assert_types::<StdioEndpoint>();
+}
@@ -0,0 +1,31 @@
+use assoc_type_params::{
+    FrameworkError, MemoryInput, MemoryOutput, StderrLog, TypeParams, TypeParamsT,
+};
+
+struct Config;
+
+#[derive(TypeParams)]
+#[type_params(
+    error = FrameworkError,
+    input = MemoryInput,
+    output = MemoryOutput,
+    state = Config,
+    logger = StderrLog,
+)]
+struct ConfiguredEndpoint;
+
+fn assert_types<T>()
+where
+    T: TypeParamsT<
+            AppError = FrameworkError,
+            Input = MemoryInput,
+            Output = MemoryOutput,
+            State = Config,
+            Logger = StderrLog,
+        >,
+{
+}
+
+fn main() {
+    assert_types::<ConfiguredEndpoint>();
+}